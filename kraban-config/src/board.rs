@@ -0,0 +1,100 @@
+use std::{fmt, fs, path::PathBuf};
+
+use color_eyre::{Result, eyre::bail};
+use kraban_lib::{Dir, find_local_dir, get_dir};
+
+use crate::Config;
+
+/// The board used without `--board` outside of directories with a `.kraban` directory
+pub const DEFAULT_BOARD: &str = "default";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Board {
+    /// Selected with `--board`. The default board is stored directly in the state dir, the others under `boards/`
+    Named(String),
+    /// A `.kraban` directory found from the working directory, it holds both the state and a config
+    Local(PathBuf)
+}
+
+impl Default for Board {
+    fn default() -> Self { Self::Named(DEFAULT_BOARD.to_string()) }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Board::Named(name) => write!(f, "{name}"),
+            Board::Local(dir) => write!(f, "{}", dir.display())
+        }
+    }
+}
+
+impl Board {
+    /// The named board if one is given, otherwise the local board if there is one
+    pub fn discover(name: Option<String>) -> Result<Self> {
+        match name {
+            Some(name) => Ok(Self::Named(name)),
+            None => Ok(find_local_dir()?.map(Self::Local).unwrap_or_default())
+        }
+    }
+
+    pub fn is_default(&self) -> bool { *self == Self::default() }
+
+    /// The config of a board is merged over the global one, the default board has none
+    pub(crate) fn overlay_path(&self) -> Result<Option<PathBuf>> {
+        let path = match self {
+            _ if self.is_default() => return Ok(None),
+            Board::Named(name) => get_dir(Dir::Config)?
+                .join("boards")
+                .join(format!("{name}.toml")),
+            Board::Local(dir) => dir.join("kraban.toml")
+        };
+
+        Ok(Some(path))
+    }
+
+    /// Board names become directory names
+    pub(crate) fn validate(&self) -> Result<()> {
+        let valid_char = |char: char| char.is_alphanumeric() || char == '-' || char == '_';
+        match self {
+            Board::Named(name) if name.is_empty() || !name.chars().all(valid_char) => {
+                bail!("Board names can only contain letters, numbers, '-' and '_', got '{name}'")
+            }
+            _ => Ok(())
+        }
+    }
+}
+
+impl Config {
+    /// Where the state of the board is stored
+    pub fn state_dir(&self) -> Result<PathBuf> {
+        let dir = match &self.board {
+            _ if self.board.is_default() => return get_dir(Dir::State),
+            Board::Named(name) => get_dir(Dir::State)?.join("boards").join(name),
+            Board::Local(dir) => return Ok(dir.clone())
+        };
+
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Lists the local board if there is one, the default board and the other boards in alphabetical order
+    pub fn boards() -> Result<Vec<Board>> {
+        let dir = get_dir(Dir::State)?.join("boards");
+        let mut names = match fs::read_dir(dir) {
+            Ok(entries) => Vec::from_iter(entries.filter_map(|entry| {
+                let entry = entry.ok()?;
+                entry.file_type().ok()?.is_dir().then_some(())?;
+                entry.file_name().into_string().ok()
+            })),
+            Err(_) => Vec::new()
+        };
+
+        names.sort();
+        let local = find_local_dir()?.map(Board::Local);
+        let named = names.into_iter().map(Board::Named);
+        Ok(Vec::from_iter(
+            local.into_iter().chain([Board::default()]).chain(named)
+        ))
+    }
+}
@@ -1,8 +1,23 @@
 use std::fs;
 
 use color_eyre::{eyre::Result, owo_colors::OwoColorize};
+use figment::{
+    Figment,
+    providers::{Data, Toml}
+};
 
-use crate::{Config, path};
+use crate::{Board, Config, path};
+
+/// The default config, ignoring the user's config file
+impl Default for Config {
+    fn default() -> Self {
+        Self::from_figment(
+            Figment::new().merge(Data::<Toml>::string(Self::DEFAULT)),
+            Board::default()
+        )
+        .expect("The default config should be valid")
+    }
+}
 
 impl Config {
     pub(crate) const DEFAULT: &str = include_str!("./default-config.toml");
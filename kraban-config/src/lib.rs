@@ -1,8 +1,10 @@
+mod board;
 mod default;
 
 use std::{ops::Deref, path::PathBuf};
 
-use color_eyre::Result;
+pub use board::{Board, DEFAULT_BOARD};
+use color_eyre::{Result, eyre::bail};
 use figment::{
     Figment,
     providers::{Data, Toml}
@@ -18,13 +20,47 @@ pub struct Config {
     pub collapse_unfocused_tabs: bool,
     pub show_key_hints: bool,
     pub always_open: AlwaysOpen,
-    pub default_due_dates: DefaultDueDates
+    pub default_due_dates: DefaultDueDates,
+    pub backups: usize,
+    pub undo_depth: usize,
+    pub trash_days: u16,
+    pub storage: StorageBackend,
+    pub git_history: bool,
+    pub wip_limit_policy: WipLimitPolicy,
+    pub tags: Vec<TagConfig>,
+    /// In the order they're shown in the priority prompt
+    pub priorities: Vec<LevelConfig>,
+    /// In the order they're shown in the difficulty prompt
+    pub difficulties: Vec<LevelConfig>,
+    /// See [`Config::state_dir`]
+    pub board: Board
 }
 
 impl Config {
+    pub fn tag_color(&self, tag: &str) -> Option<Color> {
+        self.tags
+            .iter()
+            .find(|tag_config| tag_config.name == tag)
+            .map(|tag_config| tag_config.color)
+    }
+
+    pub fn priority_level(&self, name: &str) -> Option<&LevelConfig> {
+        self.priorities.iter().find(|level| level.name == name)
+    }
+
+    pub fn difficulty_level(&self, name: &str) -> Option<&LevelConfig> {
+        self.difficulties.iter().find(|level| level.name == name)
+    }
+
     pub fn column_configs(&self) -> impl Iterator<Item = &ColumnConfig> {
         self.tabs.iter().flat_map(|tab| tab.iter())
     }
+
+    /// Columns missing from the config aren't done columns
+    pub fn is_done_column(&self, name: &str) -> bool {
+        self.column_configs()
+            .any(|column| column.done_column && column.name == name)
+    }
 }
 
 #[derive(Default, Debug)]
@@ -45,16 +81,68 @@ impl TabConfig {
 pub struct ColumnConfig {
     pub name: String,
     pub color: Color,
-    pub done_column: bool
+    pub done_column: bool,
+    pub sort: ColumnSort,
+    /// How many tasks a column of a project should hold at most
+    pub wip_limit: Option<usize>
 }
 
-#[derive(Deserialize, Debug)]
+/// How the tasks of a column are ordered
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnSort {
+    /// Highest priority first, then highest difficulty weight first, which puts the easiest tasks
+    /// first with the default difficulties
+    #[default]
+    Priority,
+    /// Soonest first, tasks without a due date last
+    DueDate,
+    Title,
+    /// Oldest first
+    Created,
+    /// Reordered by hand
+    Manual
+}
+
+#[derive(Deserialize, Clone, Debug)]
 #[serde(deny_unknown_fields)]
+pub struct TagConfig {
+    pub name: String,
+    pub color: Color
+}
+
+/// A priority or difficulty level
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LevelConfig {
+    pub name: String,
+    pub symbol: String,
+    pub color: Color,
+    /// Items with a higher weight are shown first
+    pub weight: i32,
+    /// Only used by priorities, see [`DefaultDueDates`]
+    #[serde(default)]
+    pub due_days: Option<u16>
+}
+
+/// What happens when adding a task to a column would exceed its WIP limit
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum WipLimitPolicy {
+    Warn,
+    Refuse
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    Json,
+    Sqlite
+}
+
+#[derive(Debug)]
 pub struct DefaultDueDates {
-    pub enable: bool,
-    pub high: u16,
-    pub medium: u16,
-    pub low: u16
+    pub enable: bool
 }
 
 #[derive(Deserialize, Clone, Copy, Debug)]
@@ -71,7 +159,25 @@ struct ColumnRaw {
     color: Color,
     tab: usize,
     #[serde(default)]
-    done_column: bool
+    done_column: bool,
+    #[serde(default)]
+    sort: ColumnSort,
+    #[serde(default)]
+    wip_limit: Option<usize>
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DefaultDueDatesRaw {
+    enable: bool,
+    /// Deprecated, older versions had fixed priorities with their due days set here. They now
+    /// set the `due_days` of the priority levels with the same name
+    #[serde(default)]
+    high: Option<u16>,
+    #[serde(default)]
+    medium: Option<u16>,
+    #[serde(default)]
+    low: Option<u16>
 }
 
 #[derive(Deserialize)]
@@ -79,36 +185,102 @@ struct ColumnRaw {
 struct ConfigRaw {
     #[serde(alias = "column")]
     columns: Vec<ColumnRaw>,
+    #[serde(alias = "tag", default)]
+    tags: Vec<TagConfig>,
+    #[serde(alias = "priority")]
+    priorities: Vec<LevelConfig>,
+    #[serde(alias = "difficulty")]
+    difficulties: Vec<LevelConfig>,
     app_color: Color,
     collapse_unfocused_tabs: bool,
     show_key_hints: bool,
     always_open: AlwaysOpen,
-    default_due_dates: DefaultDueDates
+    default_due_dates: DefaultDueDatesRaw,
+    backups: usize,
+    undo_depth: usize,
+    trash_days: u16,
+    storage: StorageBackend,
+    git_history: bool,
+    wip_limit_policy: WipLimitPolicy
 }
 
 impl Config {
-    pub fn new() -> Result<Self> {
-        let path = path()?;
-        let raw: ConfigRaw = Figment::new()
+    pub fn new(board: Board) -> Result<Self> {
+        board.validate()?;
+        let mut figment = Figment::new()
             .merge(Data::<Toml>::string(Self::DEFAULT))
-            .merge(Data::<Toml>::file(path))
-            .extract()?;
+            .merge(Data::<Toml>::file(path()?));
+        let overlay_path = board.overlay_path()?;
+        if let Some(overlay_path) = &overlay_path {
+            figment = figment.merge(Data::<Toml>::file(overlay_path));
+        }
+
+        let mut config = Self::from_figment(figment, board)?;
+        // The state dir of a local board is inside the repository of its project, where the
+        // history repository would be nested
+        if config.git_history
+            && let (Board::Local(dir), Some(overlay_path)) = (&config.board, overlay_path)
+        {
+            let enabled_locally = Figment::from(Data::<Toml>::file(overlay_path))
+                .extract_inner::<bool>("git_history")
+                .unwrap_or_default();
+            if enabled_locally {
+                bail!(
+                    "git_history can't be used by the local board in {}, commit the board with its project instead",
+                    dir.display()
+                )
+            }
+
+            config.git_history = false;
+        }
+
+        Ok(config)
+    }
+
+    fn from_figment(figment: Figment, board: Board) -> Result<Self> {
+        let raw: ConfigRaw = figment.extract()?;
         let ConfigRaw {
             columns,
             app_color,
             collapse_unfocused_tabs,
             show_key_hints,
             default_due_dates,
-            always_open
+            always_open,
+            backups,
+            undo_depth,
+            trash_days,
+            storage,
+            git_history,
+            wip_limit_policy,
+            tags,
+            mut priorities,
+            difficulties
         } = raw;
 
+        let DefaultDueDatesRaw {
+            enable,
+            high,
+            medium,
+            low
+        } = default_due_dates;
+        for (name, due_days) in [("high", high), ("medium", medium), ("low", low)] {
+            let level = priorities
+                .iter_mut()
+                .find(|level| level.name.eq_ignore_ascii_case(name));
+            if let (Some(level), Some(due_days)) = (level, due_days) {
+                level.due_days = Some(due_days);
+            }
+        }
+
         let columns = columns.into_iter().map(|column| {
             (
                 column.tab,
                 ColumnConfig {
                     name: column.name,
                     color: column.color,
-                    done_column: column.done_column
+                    done_column: column.done_column,
+                    sort: column.sort,
+                    wip_limit: column.wip_limit
                 }
             )
         });
@@ -127,7 +299,17 @@ impl Config {
             collapse_unfocused_tabs,
             show_key_hints,
             always_open,
-            default_due_dates
+            default_due_dates: DefaultDueDates { enable },
+            backups,
+            undo_depth,
+            trash_days,
+            storage,
+            git_history,
+            wip_limit_policy,
+            tags,
+            priorities,
+            difficulties,
+            board
         })
     }
 }
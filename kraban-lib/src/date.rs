@@ -3,6 +3,7 @@ use cli_log::error;
 use time::OffsetDateTime;
 
 pub type ChronoDate = chrono::DateTime<Local>;
+pub type Timestamp = chrono::DateTime<Local>;
 // hate that there's two crates which do not fully implement my use case but whathever
 pub fn chrono_date_to_time_date(chrono_date: ChronoDate) -> time::Date {
     let year = chrono_date.year();
@@ -30,3 +31,20 @@ pub fn now() -> time::Date {
         .unwrap_or(OffsetDateTime::now_utc())
         .date()
 }
+
+/// Formats the duration as hours and minutes, like `2h 05m`, or only minutes under an hour
+pub fn format_duration(duration: chrono::TimeDelta) -> String {
+    let minutes = duration.num_minutes();
+    match minutes / 60 {
+        0 => format!("{minutes}m"),
+        hours => format!("{hours}h {:02}m", minutes % 60)
+    }
+}
+
+/// Like [`format_duration`] but in days and hours, like `3d 04h`, for durations of a day or more
+pub fn format_elapsed(duration: chrono::TimeDelta) -> String {
+    match duration.num_days() {
+        0 => format_duration(duration),
+        days => format!("{days}d {:02}h", duration.num_hours() % 24)
+    }
+}
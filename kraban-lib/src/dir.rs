@@ -31,3 +31,13 @@ pub enum Dir {
     State,
     Config
 }
+
+/// Finds a `.kraban` directory in the working directory or one of its parents, the way git finds `.git`
+pub fn find_local_dir() -> Result<Option<PathBuf>> {
+    let dir = std::env::current_dir()?;
+    let local_dir = dir
+        .ancestors()
+        .map(|dir| dir.join(".kraban"))
+        .find(|dir| dir.is_dir());
+    Ok(local_dir)
+}
@@ -0,0 +1,27 @@
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::Path
+};
+
+use color_eyre::eyre::{ContextCompat, Result};
+
+/// Writes the file by writing a temporary file next to it, syncing it and then renaming it over the original, so that the original file is never left half written
+pub fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = path
+        .parent()
+        .wrap_err_with(|| format!("{} has no parent directory", path.display()))?;
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = dir.join(tmp_name);
+
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+
+    // Make sure the rename itself hits the disk. Directories cannot be opened on windows
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
@@ -1,8 +1,10 @@
 mod date;
 mod dir;
+mod fs;
 
 mod wrapping_usize;
 
 pub use date::*;
 pub use dir::*;
+pub use fs::*;
 pub use wrapping_usize::*;
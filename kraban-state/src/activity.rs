@@ -0,0 +1,102 @@
+use chrono::Local;
+use kraban_lib::Timestamp;
+use serde::{Deserialize, Serialize};
+use time::Date;
+
+use crate::{Difficulty, Priority, Task};
+
+/// An entry of the append-only history of a task
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct Activity {
+    pub at: Timestamp,
+    #[serde(flatten)]
+    pub change: Change
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    /// The task was created in or moved to the column
+    EnteredColumn {
+        column: String
+    },
+    Renamed {
+        from: String,
+        to: String
+    },
+    Priority {
+        from: Option<Priority>,
+        to: Option<Priority>
+    },
+    Difficulty {
+        from: Option<Difficulty>,
+        to: Option<Difficulty>
+    },
+    DueDate {
+        from: Option<Date>,
+        to: Option<Date>
+    }
+}
+
+/// The fields of a task whose changes are recorded
+pub(crate) struct Tracked {
+    title: String,
+    priority: Option<Priority>,
+    difficulty: Option<Difficulty>,
+    due_date: Option<Date>
+}
+
+impl Task {
+    /// Oldest first
+    pub fn activity(&self) -> &[Activity] { &self.activity }
+    pub(crate) fn record(&mut self, change: Change) {
+        self.activity.push(Activity {
+            at: Local::now(),
+            change
+        })
+    }
+
+    pub(crate) fn tracked(&self) -> Tracked {
+        Tracked {
+            title: self.title.clone(),
+            priority: self.priority().cloned(),
+            difficulty: self.difficulty.clone(),
+            due_date: self.due_date()
+        }
+    }
+
+    /// Records what changed since the snapshot was taken
+    pub(crate) fn record_changes(&mut self, before: Tracked) {
+        if before.title != self.title {
+            let to = self.title.clone();
+            self.record(Change::Renamed {
+                from: before.title,
+                to
+            });
+        }
+
+        if before.priority.as_ref() != self.priority() {
+            let to = self.priority().cloned();
+            self.record(Change::Priority {
+                from: before.priority,
+                to
+            });
+        }
+
+        if before.difficulty != self.difficulty {
+            let to = self.difficulty.clone();
+            self.record(Change::Difficulty {
+                from: before.difficulty,
+                to
+            });
+        }
+
+        if before.due_date != self.due_date() {
+            let to = self.due_date();
+            self.record(Change::DueDate {
+                from: before.due_date,
+                to
+            });
+        }
+    }
+}
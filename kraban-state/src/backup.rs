@@ -0,0 +1,118 @@
+use std::{fs, path::PathBuf};
+
+use chrono::Local;
+use cli_log::info;
+use color_eyre::eyre::{Context, Result, bail};
+use kraban_config::Config;
+use kraban_lib::write_atomically;
+use serde_json::Value;
+
+use crate::{State, Storage};
+
+const PREFIX: &str = "tasks-";
+const EXTENSION: &str = ".json";
+
+#[derive(Debug)]
+pub struct Backup {
+    pub name: String,
+    path: PathBuf
+}
+
+impl State {
+    /// Lists the backups of the board from newest to oldest
+    pub fn backups(config: &Config) -> Result<Vec<Backup>> {
+        let mut backups: Vec<Backup> = fs::read_dir(backups_dir(config)?)?
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let name = path.file_name()?.to_str()?.to_string();
+                (name.starts_with(PREFIX) && name.ends_with(EXTENSION))
+                    .then_some(Backup { name, path })
+            })
+            .collect();
+
+        // The timestamp format sorts lexicographically
+        backups.sort_by(|a, b| b.name.cmp(&a.name));
+        Ok(backups)
+    }
+
+    /// Replaces the stored state with the backup, backing up the current state first
+    pub fn restore_backup(name: &str, storage: &mut dyn Storage, config: &Config) -> Result<()> {
+        let backup_path = Self::backups(config)?
+            .into_iter()
+            .find(|backup| backup.name == name)
+            .map(|backup| backup.path)
+            .unwrap_or_else(|| PathBuf::from(name));
+
+        let contents = fs::read_to_string(&backup_path)
+            .wrap_err_with(|| format!("Cannot read backup {}", backup_path.display()))?;
+        let document: Value = match serde_json::from_str(&contents) {
+            Ok(document) => document,
+            Err(error) => bail!(
+                "Backup {} is not valid json: {error}",
+                backup_path.display()
+            )
+        };
+
+        Self::restore_document(document, storage, config)?;
+        Ok(())
+    }
+
+    /// Replaces the stored state with the document, backing up the current state first.
+    /// Returns the document as it was stored
+    pub(crate) fn restore_document(
+        document: Value,
+        storage: &mut dyn Storage,
+        config: &Config
+    ) -> Result<Value> {
+        // Storages other than json only accept the current version
+        let document = Self::from_document(document, config)?.to_document();
+        // A backup of the current state makes the restore itself reversible
+        backup_state_file(storage, config, config.backups.max(1))?;
+        storage.write(&document)?;
+        Ok(document)
+    }
+
+    pub(crate) fn backup_if_needed(
+        &mut self,
+        storage: &mut dyn Storage,
+        config: &Config
+    ) -> Result<()> {
+        if !self.backed_up && config.backups != 0 {
+            backup_state_file(storage, config, config.backups)?;
+        }
+
+        self.backed_up = true;
+        Ok(())
+    }
+}
+
+/// The state is always backed up before it's migrated, even with backups disabled, as older kraban versions can't read the migrated state
+pub(crate) fn backup_before_migration(storage: &mut dyn Storage, config: &Config) -> Result<()> {
+    backup_state_file(storage, config, config.backups.max(1))
+}
+
+/// Backups are json documents whatever the storage is, so they can be restored into any storage
+fn backup_state_file(storage: &mut dyn Storage, config: &Config, max_backups: usize) -> Result<()> {
+    let Some(document) = storage.read()? else {
+        return Ok(());
+    };
+
+    // Milliseconds keep a restore followed by a save in the same second from overwriting the backup of the restore
+    let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S-%3f");
+    let backup_path = backups_dir(config)?.join(format!("{PREFIX}{timestamp}{EXTENSION}"));
+    info!("Backing up state to {}", backup_path.display());
+    write_atomically(&backup_path, serde_json::to_string(&document)?.as_bytes())?;
+
+    for backup in State::backups(config)?.into_iter().skip(max_backups) {
+        info!("Removing old backup {}", backup.name);
+        fs::remove_file(backup.path)?;
+    }
+
+    Ok(())
+}
+
+fn backups_dir(config: &Config) -> Result<PathBuf> {
+    let path = config.state_dir()?.join("backups");
+    fs::create_dir_all(&path)?;
+    Ok(path)
+}
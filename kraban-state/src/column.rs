@@ -0,0 +1,72 @@
+use std::cmp::Reverse;
+
+use kraban_config::{ColumnSort, Config};
+
+use crate::{Difficulty, OrderedVec, Priority, Task};
+
+/// The tasks of a column in their manual order, the order of the other sort modes is applied by [`sort_tasks`] when the column is shown
+pub type Column = OrderedVec<Task>;
+
+/// Orders the tasks of a column as configured. The sort is stable, so tasks that compare equal keep their manual order
+pub fn sort_tasks(tasks: &mut [&Task], sort: ColumnSort, config: &Config) {
+    match sort {
+        ColumnSort::Priority => tasks.sort_by_key(|task| {
+            Reverse((
+                Priority::weight(task.priority(), config),
+                Difficulty::weight(task.difficulty.as_ref(), config)
+            ))
+        }),
+        ColumnSort::DueDate => {
+            tasks.sort_by_key(|task| (task.due_date().is_none(), task.due_date()))
+        }
+        ColumnSort::Title => tasks.sort_by_cached_key(|task| task.title.to_lowercase()),
+        ColumnSort::Created => tasks.sort_by_key(|task| task.created_at()),
+        ColumnSort::Manual => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kraban_config::{ColumnSort, Config};
+
+    use super::sort_tasks;
+    use crate::{Difficulty, Priority, SetPriority, Task};
+
+    fn task(
+        title: &str,
+        priority: Option<&str>,
+        difficulty: Option<&str>,
+        config: &Config
+    ) -> Task {
+        let mut task = Task::new(title.to_string());
+        task.set_priority(priority.map(|name| Priority::new(name.to_string())), config);
+        task.difficulty = difficulty.map(|name| Difficulty::new(name.to_string()));
+        task
+    }
+
+    #[test]
+    fn priority_sort_puts_high_priorities_then_easy_tasks_first() {
+        let config = Config::default();
+        let tasks = [
+            task("Low", Some("Low"), Some("Easy"), &config),
+            task("High hard", Some("High"), Some("Hard"), &config),
+            task("None", None, None, &config),
+            task("High easy", Some("High"), Some("Easy"), &config),
+            task("High", Some("High"), None, &config),
+            task("High normal", Some("High"), Some("Normal"), &config)
+        ];
+        let mut sorted = Vec::from_iter(&tasks);
+        sort_tasks(&mut sorted, ColumnSort::Priority, &config);
+        assert_eq!(
+            Vec::from_iter(sorted.iter().map(|task| task.title.as_str())),
+            [
+                "High easy",
+                "High normal",
+                "High hard",
+                "High",
+                "Low",
+                "None"
+            ]
+        );
+    }
+}
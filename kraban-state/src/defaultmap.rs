@@ -4,7 +4,7 @@ use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 
 /// A hashmap that always returns, even if there is no such field. In that case, it returns (and also inserts in get_mut) the default value
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
 #[serde(transparent)]
 pub struct DefaultMap<K: Eq + Hash, V> {
     inner: HashMap<K, V>,
@@ -26,6 +26,15 @@ impl<K: Eq + Hash, V: Default> DefaultMap<K, V> {
         }
     }
 
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> { self.inner.values_mut() }
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash
+    {
+        self.inner.remove(k)
+    }
+
     pub fn get<Q>(&self, k: &Q) -> &V
     where
         K: Borrow<Q>,
@@ -0,0 +1,23 @@
+use crate::{Identifiable, Project, State, Task, TaskId};
+
+impl State {
+    /// Looks the task up in every project, as tasks can be blocked by tasks in other projects
+    pub fn find_task(&self, id: TaskId) -> Option<(&Project, &Task)> {
+        self.projects.iter().find_map(|project| {
+            let task = project.tasks().find(|task| task.id() == id)?;
+            Some((project, task))
+        })
+    }
+
+    /// Blockers that haven't reached a done column yet. Deleted blockers don't block anything
+    pub fn unfinished_blockers<'a>(&'a self, task: &'a Task) -> impl Iterator<Item = &'a Task> {
+        task.blocked_by
+            .iter()
+            .filter_map(|id| Some(self.find_task(*id)?.1))
+            .filter(|blocker| blocker.completed_at().is_none())
+    }
+
+    pub fn is_blocked(&self, task: &Task) -> bool {
+        self.unfinished_blockers(task).next().is_some()
+    }
+}
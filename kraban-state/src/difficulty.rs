@@ -1,24 +1,21 @@
+use kraban_config::{Config, LevelConfig};
 use serde::{Deserialize, Serialize};
-use strum_macros::{EnumCount, EnumIter, IntoStaticStr};
 
-// Idk whether tasks should be ordered easy to hard or hard to easy,
-// but I currently stick to that easy is highest to do the easy stuff asap when I don't have motivation to do hard stuff
-#[derive(
-    Clone,
-    Copy,
-    Debug,
-    Serialize,
-    Deserialize,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    EnumCount,
-    EnumIter,
-    IntoStaticStr,
-)]
-pub enum Difficulty {
-    Hard,
-    Normal,
-    Easy
+/// The name of one of the difficulty levels in the config
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Difficulty(String);
+
+impl Difficulty {
+    pub fn new(name: String) -> Self { Self(name) }
+    pub fn name(&self) -> &str { &self.0 }
+    /// None if the level was removed from the config
+    pub fn level<'a>(&self, config: &'a Config) -> Option<&'a LevelConfig> {
+        config.difficulty_level(&self.0)
+    }
+
+    /// Levels missing from the config are weighted like no difficulty
+    pub fn weight(difficulty: Option<&Self>, config: &Config) -> Option<i32> {
+        Some(difficulty?.level(config)?.weight)
+    }
 }
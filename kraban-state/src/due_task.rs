@@ -4,17 +4,15 @@ use kraban_config::{ColumnConfig, Config};
 use time::Date;
 
 use super::State;
-use crate::{Difficulty, Priority, Project};
+use crate::{Difficulty, Priority, Project, Task};
 
 #[derive(PartialEq, Eq, Debug)]
 pub struct DueTask<'a> {
-    pub priority: Option<Priority>,
+    pub priority: Option<&'a Priority>,
     pub due_date: Date,
-    pub difficulty: Option<Difficulty>,
-    pub title: &'a str,
-    pub idx: usize,
-    pub project_idx: usize,
+    pub difficulty: Option<&'a Difficulty>,
     pub project: &'a Project,
+    pub task: &'a Task,
     pub column_config: &'a ColumnConfig
 }
 
@@ -44,7 +42,7 @@ impl<'a> State {
     fn column_due_tasks(&'a self, column: &'a ColumnConfig) -> impl Iterator<Item = DueTask<'a>> {
         self.projects
             .iter()
-            .enumerate()
-            .flat_map(|(project_idx, project)| project.due_tasks_by_column(column, project_idx))
+            .filter(|project| !project.archived)
+            .flat_map(|project| project.due_tasks_by_column(column))
     }
 }
@@ -0,0 +1,315 @@
+use chrono::{Datelike, Days, Local, NaiveDate, TimeDelta};
+use kraban_config::Config;
+use kraban_lib::Timestamp;
+
+use crate::{Change, Project, State, Task};
+
+/// Summary of how long completed tasks took
+pub struct Durations {
+    pub count: usize,
+    pub average: TimeDelta,
+    pub p50: TimeDelta,
+    pub p85: TimeDelta,
+    pub p95: TimeDelta
+}
+
+impl Durations {
+    fn new(mut durations: Vec<TimeDelta>) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+
+        durations.sort();
+        let count = durations.len();
+        // Nearest rank, so every percentile is a duration one of the tasks actually took
+        let percentile = |p: usize| durations[(p * count).div_ceil(100).max(1) - 1];
+        Some(Self {
+            count,
+            average: durations.iter().sum::<TimeDelta>() / count as i32,
+            p50: percentile(50),
+            p85: percentile(85),
+            p95: percentile(95)
+        })
+    }
+}
+
+/// Lead time goes from the creation of a task to its completion,
+/// cycle time from when it left the backlog (the first configured column) to its completion
+pub struct FlowTimes {
+    pub lead: Option<Durations>,
+    pub cycle: Option<Durations>
+}
+
+impl FlowTimes {
+    fn new<'f, 't: 'f>(flows: impl IntoIterator<Item = &'f TaskFlow<'t>>) -> Self {
+        let (lead, cycle): (Vec<_>, Vec<_>) = flows
+            .into_iter()
+            .map(|flow| (flow.done - flow.task.created_at(), flow.cycle_time()))
+            .unzip();
+
+        Self {
+            lead: Durations::new(lead),
+            cycle: Durations::new(cycle.into_iter().flatten().collect())
+        }
+    }
+}
+
+pub struct FlowReport<'a> {
+    /// Only the projects with completed tasks
+    pub projects: Vec<(&'a Project, FlowTimes)>,
+    /// In the order of the config, tasks without a difficulty last
+    pub difficulties: Vec<(Option<&'a str>, FlowTimes)>,
+    /// Tasks completed each week, by the monday starting it, oldest first
+    pub throughput: Vec<(NaiveDate, usize)>
+}
+
+struct TaskFlow<'a> {
+    task: &'a Task,
+    started: Option<Timestamp>,
+    done: Timestamp
+}
+
+impl TaskFlow<'_> {
+    fn cycle_time(&self) -> Option<TimeDelta> { Some(self.done - self.started?) }
+}
+
+impl Task {
+    /// When the task last entered a done column, if it's in one.
+    /// Tasks completed before their activity was recorded fall back to [`Task::completed_at`]
+    fn done_at(&self, config: &Config) -> Option<Timestamp> {
+        let completed_at = self.completed_at?;
+        let entered_done = self
+            .activity
+            .iter()
+            .rev()
+            .find_map(|activity| match &activity.change {
+                Change::EnteredColumn { column } if config.is_done_column(column) => {
+                    Some(activity.at)
+                }
+                _ => None
+            });
+        Some(entered_done.unwrap_or(completed_at))
+    }
+
+    /// When the task first entered a column other than the backlog
+    fn started_at(&self, config: &Config) -> Option<Timestamp> {
+        let backlog = config.column_configs().next()?;
+        self.activity
+            .iter()
+            .find_map(|activity| match &activity.change {
+                Change::EnteredColumn { column } if *column != backlog.name => Some(activity.at),
+                _ => None
+            })
+    }
+}
+
+impl State {
+    /// Lead and cycle times of the completed tasks and how many were completed in each of the last weeks
+    pub fn flow_report(&self, weeks: u32, config: &Config) -> FlowReport<'_> {
+        let project_flows = Vec::from_iter(self.projects.iter().map(|project| {
+            let flows = project.tasks().filter_map(|task| {
+                Some(TaskFlow {
+                    task,
+                    started: task.started_at(config),
+                    done: task.done_at(config)?
+                })
+            });
+            (project, Vec::from_iter(flows))
+        }));
+        let flows = || project_flows.iter().flat_map(|(_, flows)| flows);
+
+        let projects = project_flows
+            .iter()
+            .filter(|(_, flows)| !flows.is_empty())
+            .map(|(project, flows)| (*project, FlowTimes::new(flows)))
+            .collect();
+
+        let mut difficulties = Vec::<(Option<&str>, Vec<&TaskFlow>)>::new();
+        for flow in flows() {
+            let difficulty = flow
+                .task
+                .difficulty
+                .as_ref()
+                .map(|difficulty| difficulty.name());
+            match difficulties
+                .iter_mut()
+                .find(|(name, _)| *name == difficulty)
+            {
+                Some((_, flows)) => flows.push(flow),
+                None => difficulties.push((difficulty, vec![flow]))
+            }
+        }
+
+        difficulties.sort_by_key(|(name, _)| match name {
+            Some(name) => config
+                .difficulties
+                .iter()
+                .position(|level| level.name == *name)
+                .unwrap_or(usize::MAX - 1),
+            None => usize::MAX
+        });
+
+        let week_start =
+            |date: NaiveDate| date - Days::new(date.weekday().num_days_from_monday().into());
+        let this_week = week_start(Local::now().date_naive());
+        let throughput = (0..weeks)
+            .rev()
+            .map(|weeks_ago| {
+                let week = this_week - Days::new(7 * u64::from(weeks_ago));
+                let done = flows()
+                    .filter(|flow| week_start(flow.done.date_naive()) == week)
+                    .count();
+                (week, done)
+            })
+            .collect();
+
+        FlowReport {
+            projects,
+            difficulties: difficulties
+                .into_iter()
+                .map(|(name, flows)| (name, FlowTimes::new(flows)))
+                .collect(),
+            throughput
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta};
+    use kraban_config::Config;
+    use kraban_lib::Timestamp;
+    use serde_json::json;
+
+    use super::{Durations, TaskFlow};
+    use crate::{Project, State, Task};
+
+    fn hours(hours: i64) -> TimeDelta { TimeDelta::hours(hours) }
+
+    /// Hours after an arbitrary start
+    fn at(hours: i64) -> Timestamp {
+        let start: Timestamp = DateTime::parse_from_rfc3339("2026-10-05T09:00:00Z")
+            .unwrap()
+            .into();
+        start + self::hours(hours)
+    }
+
+    /// A task created in the first column and moved through the others at the given hours
+    fn task(columns: &[(i64, &str)], completed: bool) -> Task {
+        let mut task = serde_json::to_value(Task::new("Task".to_string())).unwrap();
+        task["created_at"] = json!(at(columns[0].0));
+        task["completed_at"] = json!(completed.then(|| at(columns.last().unwrap().0)));
+        task["activity"] = json!(Vec::from_iter(columns.iter().map(|(hours, column)| {
+            json!({ "at": at(*hours), "change": "entered_column", "column": column })
+        })));
+        serde_json::from_value(task).unwrap()
+    }
+
+    fn flow<'a>(task: &'a Task, config: &Config) -> TaskFlow<'a> {
+        TaskFlow {
+            task,
+            started: task.started_at(config),
+            done: task.done_at(config).unwrap()
+        }
+    }
+
+    fn percentiles(durations: &Durations) -> [TimeDelta; 4] {
+        [
+            durations.average,
+            durations.p50,
+            durations.p85,
+            durations.p95
+        ]
+    }
+
+    #[test]
+    fn percentiles_of_a_single_task_are_its_duration() {
+        let durations = Durations::new(vec![hours(5)]).unwrap();
+        assert_eq!(durations.count, 1);
+        assert_eq!(percentiles(&durations), [hours(5); 4]);
+        assert!(Durations::new(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn percentiles_of_two_tasks_are_nearest_rank() {
+        let durations = Durations::new(vec![hours(3), hours(1)]).unwrap();
+        assert_eq!(
+            percentiles(&durations),
+            [hours(2), hours(1), hours(3), hours(3)]
+        );
+    }
+
+    #[test]
+    fn percentiles_of_twenty_tasks_are_nearest_rank() {
+        let durations = Durations::new(Vec::from_iter((1..=20).rev().map(hours))).unwrap();
+        assert_eq!(durations.count, 20);
+        assert_eq!(
+            percentiles(&durations),
+            [
+                TimeDelta::minutes(10 * 60 + 30),
+                hours(10),
+                hours(17),
+                hours(19)
+            ]
+        );
+    }
+
+    #[test]
+    fn cycle_time_starts_when_the_task_leaves_the_backlog() {
+        let config = Config::default();
+        let task = task(&[(0, "Backlog"), (10, "Doing"), (15, "Done")], true);
+        let flow = flow(&task, &config);
+        assert_eq!(flow.done - task.created_at(), hours(15));
+        assert_eq!(flow.cycle_time(), Some(hours(5)));
+    }
+
+    #[test]
+    fn tasks_created_past_the_backlog_start_when_created() {
+        let config = Config::default();
+        let task = task(&[(0, "Doing"), (4, "Done")], true);
+        let flow = flow(&task, &config);
+        assert_eq!(flow.started, Some(at(0)));
+        assert_eq!(flow.cycle_time(), Some(hours(4)));
+    }
+
+    #[test]
+    fn reopened_tasks_are_done_when_they_last_entered_a_done_column() {
+        let config = Config::default();
+        let task = task(
+            &[
+                (0, "Backlog"),
+                (1, "Doing"),
+                (2, "Done"),
+                (10, "Doing"),
+                (12, "Done")
+            ],
+            true
+        );
+        assert_eq!(task.done_at(&config), Some(at(12)));
+        assert_eq!(flow(&task, &config).cycle_time(), Some(hours(11)));
+    }
+
+    #[test]
+    fn only_completed_tasks_are_reported() {
+        let config = Config::default();
+        let mut project = Project::new("Project".to_string());
+        project
+            .columns
+            .get_mut("Done")
+            .push(task(&[(0, "Backlog"), (2, "Doing"), (8, "Done")], true));
+        project
+            .columns
+            .get_mut("Doing")
+            .push(task(&[(0, "Backlog"), (1, "Doing")], false));
+        let mut state = State::default();
+        state.projects_mut().push(project);
+
+        let report = state.flow_report(1, &config);
+        let [(_, times)] = report.projects.as_slice() else {
+            panic!("Expected a single project")
+        };
+        assert_eq!(times.lead.as_ref().unwrap().count, 1);
+        assert_eq!(times.lead.as_ref().unwrap().p50, hours(8));
+        assert_eq!(times.cycle.as_ref().unwrap().p50, hours(6));
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use chrono::{Local, TimeZone};
+use cli_log::info;
+use color_eyre::{
+    Result,
+    eyre::{Context, OptionExt, bail}
+};
+use git2::{ErrorCode, IndexAddOption, Repository, Signature};
+use kraban_config::Config;
+use kraban_lib::{Timestamp, write_atomically};
+use serde_json::Value;
+
+use crate::{State, Storage};
+
+/// The repository has a directory of its own, so that it doesn't pick up the backups or the
+/// storage files of the state dir
+const HISTORY_DIR: &str = "history";
+/// The only file in the repository, a copy of the state written on every commit
+const STATE_FILE: &str = "tasks.json";
+
+#[derive(Debug)]
+pub struct Commit {
+    pub id: String,
+    pub time: Timestamp,
+    pub message: String
+}
+
+impl State {
+    /// Lists the commits of the state from newest to oldest
+    pub fn git_history(config: &Config) -> Result<Vec<Commit>> {
+        let repository = open_repository(&config.state_dir()?.join(HISTORY_DIR))?;
+        let mut revwalk = repository.revwalk()?;
+        match revwalk.push_head() {
+            Err(error) if error.code() == ErrorCode::UnbornBranch => return Ok(Vec::new()),
+            result => result?
+        }
+
+        revwalk
+            .map(|id| {
+                let commit = repository.find_commit(id?)?;
+                let time = Local
+                    .timestamp_opt(commit.time().seconds(), 0)
+                    .single()
+                    .ok_or_eyre("Commit time out of range")?;
+                Ok(Commit {
+                    id: commit
+                        .as_object()
+                        .short_id()?
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    time,
+                    message: commit.summary().unwrap_or_default().to_string()
+                })
+            })
+            .collect()
+    }
+
+    /// Replaces the stored state with the one committed in `revision` and commits the result
+    pub fn checkout_git_revision(
+        revision: &str,
+        storage: &mut dyn Storage,
+        config: &Config
+    ) -> Result<()> {
+        let dir = config.state_dir()?;
+        let repository = open_repository(&dir.join(HISTORY_DIR))?;
+        let commit = repository
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_commit())
+            .wrap_err_with(|| format!("Cannot find revision '{revision}'"))?;
+        let blob = commit
+            .tree()?
+            .get_path(Path::new(STATE_FILE))?
+            .to_object(&repository)?
+            .peel_to_blob()?;
+        let document: Value = serde_json::from_slice(blob.content())
+            .wrap_err_with(|| format!("The state in revision '{revision}' is not valid json"))?;
+
+        let document = Self::restore_document(document, storage, config)?;
+        let id = commit.as_object().short_id()?;
+        commit_state(
+            &dir,
+            &document,
+            &format!("check out {}", id.as_str().unwrap_or(revision))
+        )
+    }
+}
+
+/// Commits a copy of the state document to the repository in the history dir of the state dir,
+/// creating it on the first commit. Nothing is committed if the state didn't change
+pub(crate) fn commit_state(dir: &Path, document: &Value, description: &str) -> Result<()> {
+    let dir = dir.join(HISTORY_DIR);
+    let repository = match Repository::open(&dir) {
+        Ok(repository) => repository,
+        Err(error) if error.code() == ErrorCode::NotFound => {
+            info!("Creating a git repository in {}", dir.display());
+            Repository::init(&dir)?
+        }
+        Err(error) => Err(error)?
+    };
+
+    // Pretty printed so that the commits diff well
+    let contents = serde_json::to_string_pretty(document)?;
+    write_atomically(&dir.join(STATE_FILE), contents.as_bytes())?;
+
+    let mut index = repository.index()?;
+    index.add_all([STATE_FILE], IndexAddOption::DEFAULT, None)?;
+    index.write()?;
+    let tree = repository.find_tree(index.write_tree()?)?;
+
+    let parent = match repository.head() {
+        Ok(head) => Some(head.peel_to_commit()?),
+        Err(error) if error.code() == ErrorCode::UnbornBranch => None,
+        Err(error) => Err(error)?
+    };
+
+    if parent
+        .as_ref()
+        .is_some_and(|parent| parent.tree_id() == tree.id())
+    {
+        return Ok(());
+    }
+
+    // Plain local repositories may not have a user configured
+    let signature = repository
+        .signature()
+        .or_else(|_| Signature::now("kraban", "kraban@localhost"))?;
+    repository.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &capitalize(description),
+        &tree,
+        Vec::from_iter(&parent).as_slice()
+    )?;
+
+    Ok(())
+}
+
+fn open_repository(dir: &Path) -> Result<Repository> {
+    match Repository::open(dir) {
+        Ok(repository) => Ok(repository),
+        Err(error) if error.code() == ErrorCode::NotFound => {
+            bail!("There is no history yet, enable git_history in the config first")
+        }
+        Err(error) => Err(error)?
+    }
+}
+
+fn capitalize(description: &str) -> String {
+    let mut chars = description.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,100 @@
+use std::{collections::VecDeque, mem};
+
+use kraban_config::Config;
+
+use crate::{Projects, State, Trash};
+
+/// Snapshots of the projects and the trash taken before every change, used for undo and redo
+#[derive(Default, Debug)]
+pub(crate) struct History {
+    undo: VecDeque<Change>,
+    redo: Vec<Change>,
+    pending: Option<Change>,
+    /// Descriptions of the changes made since the last save
+    unsaved: Vec<String>
+}
+
+#[derive(Debug)]
+pub(crate) struct Change {
+    projects: Projects,
+    trash: Trash,
+    description: String
+}
+
+impl History {
+    /// Returns the change currently being made, snapshotting the projects and the trash if it has just started
+    pub(crate) fn snapshot_if_needed(&mut self, projects: &Projects, trash: &Trash) -> &mut Change {
+        self.pending.get_or_insert_with(|| Change {
+            projects: projects.clone(),
+            trash: trash.clone(),
+            description: "edit".to_string()
+        })
+    }
+}
+
+impl State {
+    /// Sets the description shown when the current change is undone or redone
+    pub fn describe_change(&mut self, description: String) {
+        self.history
+            .snapshot_if_needed(&self.projects, &self.trash)
+            .description = description;
+    }
+
+    /// Describes a change that can't be undone, so that it's still part of the description of the next save
+    pub(crate) fn describe_saved_change(&mut self, description: String) {
+        self.should_save = true;
+        self.history.unsaved.push(description);
+    }
+
+    /// Should be called after every event, so that a change consisting of multiple modifications is undone at once
+    pub fn finish_change(&mut self, config: &Config) {
+        let Some(change) = self.history.pending.take() else {
+            return
+        };
+
+        self.history.redo.clear();
+        self.history.unsaved.push(change.description.clone());
+        self.history.undo.push_back(change);
+        if self.history.undo.len() > config.undo_depth {
+            self.history.undo.pop_front();
+        }
+    }
+
+    /// Returns the description of the undone change
+    pub fn undo(&mut self) -> Option<String> {
+        let change = self.history.undo.pop_back()?;
+        let change = self.swap_change(change);
+        let description = change.description.clone();
+        self.history.unsaved.push(format!("undid {description}"));
+        self.history.redo.push(change);
+        Some(description)
+    }
+
+    /// Returns the description of the redone change
+    pub fn redo(&mut self) -> Option<String> {
+        let change = self.history.redo.pop()?;
+        let change = self.swap_change(change);
+        let description = change.description.clone();
+        self.history.unsaved.push(format!("redid {description}"));
+        self.history.undo.push_back(change);
+        Some(description)
+    }
+
+    /// Describes everything changed since the last save, used as the commit message of the save
+    pub(crate) fn take_unsaved_description(&mut self) -> String {
+        let description = match self.history.unsaved.as_slice() {
+            [] => "edit".to_string(),
+            descriptions => descriptions.join(", ")
+        };
+
+        self.history.unsaved.clear();
+        description
+    }
+
+    fn swap_change(&mut self, mut change: Change) -> Change {
+        mem::swap(&mut self.projects, &mut change.projects);
+        mem::swap(&mut self.trash, &mut change.trash);
+        self.should_save = true;
+        change
+    }
+}
@@ -0,0 +1,29 @@
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An item which can be found regardless of its position, as positions change whenever items are re-sorted
+pub trait Identifiable {
+    type Id: Copy + Eq + Debug;
+    fn id(&self) -> Self::Id;
+}
+
+macro_rules! id {
+    ($name:ident) => {
+        #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        #[serde(transparent)]
+        pub struct $name(Uuid);
+        impl $name {
+            pub fn new() -> Self { Self(Uuid::new_v4()) }
+        }
+
+        impl Default for $name {
+            fn default() -> Self { Self::new() }
+        }
+    };
+}
+
+id!(ProjectId);
+id!(TaskId);
+id!(TrashId);
@@ -1,69 +1,120 @@
 //TODO: Use defaultmap as a dependency when https://github.com/JelteF/defaultmap/issues/19 is resolved
+mod activity;
+mod backup;
+mod column;
 mod defaultmap;
+mod dependency;
 mod difficulty;
 mod due_task;
+mod flow;
+mod git;
+mod history;
+mod id;
 mod migration;
+mod ordered_vec;
+mod orphan;
 mod priority;
 mod project;
-mod sorted_vec;
+mod recurrence;
+mod storage;
 mod task;
+mod template;
+mod timer;
+mod transfer;
+mod trash;
 
-use std::{fs, io::ErrorKind, path::PathBuf};
+use std::cmp::Reverse;
 
-use color_eyre::Result;
+pub use activity::{Activity, Change};
+pub use backup::Backup;
+use backup::backup_before_migration;
+use color_eyre::{Result, eyre::Context};
+pub use column::{Column, sort_tasks};
 pub use difficulty::Difficulty;
 pub use due_task::DueTask;
+pub use flow::{Durations, FlowReport, FlowTimes};
+pub use git::Commit;
+use git::commit_state;
+pub use id::{Identifiable, ProjectId, TaskId, TrashId};
 use kraban_config::Config;
-use kraban_lib::{Dir, get_dir};
+pub use ordered_vec::OrderedVec;
+pub use orphan::OrphanedColumn;
 pub use priority::{Priority, SetPriority};
 pub use project::Project;
+pub use recurrence::Recurrence;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
-pub use sorted_vec::ReversedSortedVec;
-pub use task::Task;
+#[cfg(feature = "sqlite")]
+pub use storage::SqliteStorage;
+pub use storage::{JsonStorage, Storage, open, open_storage};
+pub use task::{ChecklistItem, Task};
+pub use template::{Template, TemplateTask};
+pub use timer::{ProjectTime, WorkInterval};
+pub use trash::{Trash, TrashItem, Trashed};
 
-use crate::defaultmap::DefaultMap;
+use crate::{defaultmap::DefaultMap, history::History};
 
 pub type Columns = DefaultMap<String, Column>;
-pub type Column = ReversedSortedVec<Task>;
-pub type Projects = ReversedSortedVec<Project>;
+pub type Projects = OrderedVec<Project>;
 #[derive(Serialize, Deserialize, Default, Debug)]
 pub struct State {
     projects: Projects,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    trash: Trash,
+    /// Not part of the undo history, see [`State::save_template`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    templates: Vec<Template>,
     #[serde(skip)]
-    should_save: bool
+    should_save: bool,
+    #[serde(skip)]
+    backed_up: bool,
+    #[serde(skip)]
+    history: History
 }
 
 impl State {
-    pub fn new(config: &Config) -> Result<Self> {
-        let mut value: Value = serde_json::from_str(
-            match fs::read_to_string(path()?) {
-                Ok(contents) => contents,
-                Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::default()),
-                error => error?
-            }
-            .as_str()
-        )?;
-
-        if value.is_null() {
+    pub fn new(storage: &mut dyn Storage, config: &Config) -> Result<Self> {
+        let Some(document) = storage.read()? else {
             return Ok(Self::default());
+        };
+
+        if document_version(&document) < Self::CURRENT_VERSION {
+            backup_before_migration(storage, config)?;
         }
 
-        let version = value["version"].as_u64().unwrap_or(Self::BASILK_VERSION);
+        Self::from_document(document, config)
+    }
+
+    /// Migrates and parses a document as stored by [`Storage`]
+    pub(crate) fn from_document(mut document: Value, config: &Config) -> Result<Self> {
+        let version = document_version(&document);
         let state = match version {
-            Self::BASILK_VERSION => value,
-            _ => value["state"].take()
+            Self::BASILK_VERSION => document,
+            _ => document["state"].take()
         };
 
-        Self::from_version(version, state, config)
+        let state = Self::migrate(version, state, config)?;
+        let mut state: Self = serde_json::from_value(state)
+            .wrap_err("The state file doesn't match the current version")?;
+        state.backed_up = version < Self::CURRENT_VERSION;
+        state.purge_expired_trash(config);
+        Ok(state)
+    }
+
+    pub(crate) fn to_document(&self) -> Value {
+        json!({"version": Self::CURRENT_VERSION, "state": self})
     }
 
-    pub fn save_if_needed(&mut self) -> Result<()> {
+    pub fn save_if_needed(&mut self, storage: &mut dyn Storage, config: &Config) -> Result<()> {
         if self.should_save {
-            let json = json!({"version": Self::CURRENT_VERSION, "state": self});
-            let contents = serde_json::to_string(&json)?;
-            let path = path()?;
-            fs::write(path, contents)?;
+            self.backup_if_needed(storage, config)?;
+            let document = self.to_document();
+            storage.write(&document)?;
+            let description = self.take_unsaved_description();
+            if config.git_history {
+                commit_state(&config.state_dir()?, &document, &description)?;
+            }
+
             self.should_save = false;
         }
 
@@ -71,8 +122,17 @@ impl State {
     }
 
     pub fn projects(&self) -> &Projects { &self.projects }
+    pub fn project(&self, id: ProjectId) -> Option<&Project> { self.projects.get_by_id(id) }
+    /// Highest priority first, projects with the same priority stay in the order they were added in
+    pub fn sorted_projects(&self, config: &Config) -> impl Iterator<Item = &Project> {
+        let mut projects = Vec::from_iter(self.projects.iter());
+        projects
+            .sort_by_key(|project| Reverse(Priority::weight(project.priority.as_ref(), config)));
+        projects.into_iter()
+    }
     pub fn projects_mut(&mut self) -> &mut Projects {
         self.should_save = true;
+        self.history.snapshot_if_needed(&self.projects, &self.trash);
         &mut self.projects
     }
 }
@@ -80,17 +140,18 @@ impl State {
 #[derive(strum_macros::AsRefStr, Debug)]
 #[strum(serialize_all = "lowercase")]
 pub enum CurrentItem<'a> {
-    Project(Option<usize>),
-    DueTask(Option<usize>),
+    Project(Option<ProjectId>),
+    DueTask(Option<TaskId>),
     Task {
-        project: usize,
+        project: ProjectId,
         column: &'a str,
-        task: Option<usize>
+        task: Option<TaskId>
     }
 }
 
-fn path() -> Result<PathBuf> {
-    let mut path = get_dir(Dir::State)?;
-    path.push("tasks.json");
-    Ok(path)
+/// Basilk files are a bare list of projects without a version
+fn document_version(document: &Value) -> u64 {
+    document["version"]
+        .as_u64()
+        .unwrap_or(State::BASILK_VERSION)
 }
@@ -1,85 +1,345 @@
-use core::panic;
+use std::{cmp::Reverse, collections::BTreeMap};
 
+use chrono::Local;
 use cli_log::info;
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Context, Result, bail, eyre};
 use kraban_config::Config;
-use serde_json::Value;
+use serde_json::{Map, Value, json};
 
-use super::{
-    Priority, Project, State, Task, defaultmap::DefaultMap, sorted_vec::ReversedSortedVec
-};
-use crate::SetPriority;
+use super::{Priority, State, Task};
+use crate::{ProjectId, SetPriority, TaskId};
 
 impl State {
     pub const BASILK_VERSION: u64 = 0;
-    // IMPORTANT: update this everytime `State` is updated incompatibly
-    pub const CURRENT_VERSION: u64 = 1;
+    // IMPORTANT: update this everytime `State` is updated incompatibly and add a step to `migration_step`
+    pub const CURRENT_VERSION: u64 = 3;
 
-    #[allow(clippy::match_overlapping_arm)]
-    pub(super) fn from_version(version: u64, value: Value, config: &Config) -> Result<Self> {
+    /// Brings the state to the current version one step at a time
+    pub(super) fn migrate(version: u64, mut value: Value, config: &Config) -> Result<Value> {
         info!(
             "Json version {version}, latest version {}",
             Self::CURRENT_VERSION
         );
 
-        match version {
-            Self::BASILK_VERSION => Ok(Self::from_basilk(value, config)),
-            Self::CURRENT_VERSION => Ok(serde_json::from_value(value)?),
-            Self::CURRENT_VERSION.. => unreachable!()
+        if version > Self::CURRENT_VERSION {
+            bail!(
+                "The state file has version {version}, but this kraban only supports versions up to {}. Please update kraban",
+                Self::CURRENT_VERSION
+            )
         }
-    }
 
-    pub(super) fn from_basilk(mut value: Value, config: &Config) -> Self {
-        let projects = value
-            .as_array_mut()
-            .unwrap()
-            .iter_mut()
-            .map(|basilk_project| process_basilk_project(basilk_project, config))
-            .collect();
-        Self {
-            projects,
-            should_save: false
+        for from in version..Self::CURRENT_VERSION {
+            value = migration_step(from, value, config).wrap_err_with(|| {
+                format!(
+                    "Cannot migrate the state file from version {from} to {}",
+                    from + 1
+                )
+            })?;
         }
+
+        Ok(value)
     }
 }
 
-fn process_basilk_project(basilk_project: &mut Value, config: &Config) -> Project {
-    let mut columns: DefaultMap<String, ReversedSortedVec<Task>> = DefaultMap::with_capacity(3);
-    basilk_project["tasks"]
-        .as_array_mut()
-        .unwrap()
-        .iter_mut()
+/// Migrates the state from version `from` to the next one
+fn migration_step(from: u64, value: Value, config: &Config) -> Result<Value> {
+    match from {
+        State::BASILK_VERSION => from_basilk(value, config),
+        1 => add_ids(value),
+        2 => add_timestamps(value, config),
+        _ => bail!("There is no migration from version {from}")
+    }
+}
+
+/// Version 1 is the first kraban version, basilk stores a list of projects with tasks that have a status instead of a column
+fn from_basilk(value: Value, config: &Config) -> Result<Value> {
+    let Value::Array(basilk_projects) = value else {
+        bail!("Basilk state should be a list of projects")
+    };
+
+    let projects: Vec<Value> = basilk_projects
+        .iter()
+        .map(|basilk_project| process_basilk_project(basilk_project, config))
+        .collect::<Result<_>>()?;
+    Ok(json!({ "projects": projects }))
+}
+
+fn process_basilk_project(basilk_project: &Value, config: &Config) -> Result<Value> {
+    let title = str_field(basilk_project, "title", "basilk project")?;
+    let Some(basilk_tasks) = basilk_project["tasks"].as_array() else {
+        bail!("Basilk project '{title}' has no list of tasks")
+    };
+
+    let mut tasks: Vec<_> = basilk_tasks
+        .iter()
         .map(|basilk_task| process_basilk_task(basilk_task, config))
-        .for_each(|(column, task)| _ = columns.get_mut(column).push(task));
+        .collect::<Result<_>>()
+        .wrap_err_with(|| format!("Invalid task in basilk project '{title}'"))?;
 
-    let title = basilk_project["title"].as_str().unwrap().to_string();
-    Project {
-        title,
-        columns,
-        ..Project::default()
+    // Columns are sorted from the highest priority
+    tasks.sort_by_key(|(_, priority, _)| Reverse(Priority::weight(priority.as_ref(), config)));
+    let mut columns: BTreeMap<&str, Vec<Value>> = BTreeMap::new();
+    for (column, _, task) in tasks {
+        columns.entry(column).or_default().push(task);
     }
+
+    Ok(json!({ "priority": null, "title": title, "columns": columns }))
 }
 
-fn process_basilk_task(basilk_task: &mut Value, config: &Config) -> (&'static str, Task) {
-    // TODO: return result instead of panicking
-    let mut task = Task::new(basilk_task["title"].as_str().unwrap().to_string());
-    task.set_priority(
-        match basilk_task["priority"].as_u64().unwrap() {
-            0 => None,
-            1 => Some(Priority::Low),
-            2 => Some(Priority::Medium),
-            3 => Some(Priority::High),
-            _ => panic!()
-        },
-        config
-    );
-
-    let column_name = match basilk_task["status"].as_str().unwrap() {
-        "UpNext" => "Backlog",
-        "OnGoing" => "Doing",
-        "Done" => "Done",
-        _ => panic!("Not valid basilk state")
+/// Returns the column, priority and the task
+fn process_basilk_task(
+    basilk_task: &Value,
+    config: &Config
+) -> Result<(&'static str, Option<Priority>, Value)> {
+    let title = str_field(basilk_task, "title", "basilk task")?;
+    let priority = match basilk_task["priority"].as_u64() {
+        Some(0) => None,
+        Some(priority @ 1..=3) => basilk_priority(priority as usize, config),
+        _ => bail!(
+            "Task '{title}' has an invalid priority {}",
+            basilk_task["priority"]
+        )
     };
 
-    (column_name, task)
+    let column = match basilk_task["status"].as_str() {
+        Some("UpNext") => "Backlog",
+        Some("OnGoing") => "Doing",
+        Some("Done") => "Done",
+        _ => bail!(
+            "Task '{title}' has an invalid status {}",
+            basilk_task["status"]
+        )
+    };
+
+    // Sets the default due date if enabled
+    let mut task = Task::new(title.to_string());
+    task.set_priority(priority.clone(), config);
+    let task = json!({
+        "priority": priority,
+        "due_date": task.due_date(),
+        "difficulty": null,
+        "title": title,
+        "due_date_manually_set": false
+    });
+
+    Ok((column, priority, task))
+}
+
+/// Maps the low, medium and high priority of basilk (1 to 3) onto the configured levels from the lowest to the highest weight
+fn basilk_priority(priority: usize, config: &Config) -> Option<Priority> {
+    let mut levels = Vec::from_iter(&config.priorities);
+    levels.sort_by_key(|level| level.weight);
+    let idx = (priority - 1) * levels.len().checked_sub(1)? / 2;
+    Some(Priority::new(levels[idx].name.clone()))
+}
+
+/// Version 2 identifies projects and tasks by ids instead of their positions and numbers tasks within projects
+fn add_ids(mut state: Value) -> Result<Value> {
+    for project in projects_mut(&mut state)? {
+        let project = as_object_mut(project, "project")?;
+        let mut last_task_number = 0;
+        for tasks in columns_mut(project)?.values_mut() {
+            for task in as_array_mut(tasks, "column")? {
+                last_task_number += 1;
+                let task = as_object_mut(task, "task")?;
+                task.insert("id".to_string(), json!(TaskId::new()));
+                task.insert("number".to_string(), json!(last_task_number));
+            }
+        }
+
+        project.insert("id".to_string(), json!(ProjectId::new()));
+        project.insert("last_task_number".to_string(), json!(last_task_number));
+    }
+
+    Ok(state)
+}
+
+/// Version 3 adds creation, update and completion timestamps to tasks. As the real ones are unknown, the time of the migration is used
+fn add_timestamps(mut state: Value, config: &Config) -> Result<Value> {
+    let now = json!(Local::now());
+    for project in projects_mut(&mut state)? {
+        let project = as_object_mut(project, "project")?;
+        for (column, tasks) in columns_mut(project)? {
+            let done = config.is_done_column(column);
+            for task in as_array_mut(tasks, "column")? {
+                let task = as_object_mut(task, "task")?;
+                task.insert("created_at".to_string(), now.clone());
+                task.insert("updated_at".to_string(), now.clone());
+                if done {
+                    task.insert("completed_at".to_string(), now.clone());
+                }
+            }
+        }
+    }
+
+    Ok(state)
+}
+
+fn str_field<'a>(value: &'a Value, field: &str, what: &str) -> Result<&'a str> {
+    value[field]
+        .as_str()
+        .ok_or_else(|| eyre!("The {what} {value} has no {field}"))
+}
+
+fn projects_mut(state: &mut Value) -> Result<&mut Vec<Value>> {
+    let projects = as_object_mut(state, "state")?
+        .get_mut("projects")
+        .ok_or_else(|| eyre!("The state has no projects"))?;
+    as_array_mut(projects, "projects")
+}
+
+fn columns_mut(project: &mut Map<String, Value>) -> Result<&mut Map<String, Value>> {
+    let title = project
+        .get("title")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    project
+        .get_mut("columns")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| eyre!("The project '{title}' has no columns"))
+}
+
+fn as_object_mut<'a>(value: &'a mut Value, what: &str) -> Result<&'a mut Map<String, Value>> {
+    match value {
+        Value::Object(object) => Ok(object),
+        value => bail!("Expected the {what} to be an object, found {value}")
+    }
+}
+
+fn as_array_mut<'a>(value: &'a mut Value, what: &str) -> Result<&'a mut Vec<Value>> {
+    match value {
+        Value::Array(array) => Ok(array),
+        value => bail!("Expected the {what} to be a list, found {value}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kraban_config::{Config, LevelConfig};
+    use serde_json::{Value, json};
+
+    use super::{add_ids, add_timestamps, from_basilk};
+    use crate::State;
+
+    fn basilk_state() -> Value {
+        json!([{
+            "title": "Project",
+            "tasks": [
+                { "title": "Low", "priority": 1, "status": "UpNext" },
+                { "title": "High", "priority": 3, "status": "UpNext" },
+                { "title": "Finished", "priority": 0, "status": "Done" }
+            ]
+        }])
+    }
+
+    fn titles(column: &Value) -> Vec<&str> {
+        column
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|task| task["title"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn basilk_tasks_are_sorted_into_columns() {
+        let state = from_basilk(basilk_state(), &Config::default()).unwrap();
+        let columns = &state["projects"][0]["columns"];
+        assert_eq!(state["projects"][0]["title"], "Project");
+        assert_eq!(titles(&columns["Backlog"]), ["High", "Low"]);
+        assert_eq!(titles(&columns["Done"]), ["Finished"]);
+        assert_eq!(columns["Backlog"][0]["priority"], "High");
+    }
+
+    #[test]
+    fn basilk_priorities_map_onto_configured_levels() {
+        let mut config = Config::default();
+        let level = |name: &str, weight| LevelConfig {
+            name: name.to_string(),
+            weight,
+            ..config.priorities[0].clone()
+        };
+        config.priorities = vec![level("Top", 9), level("Bottom", 0), level("Middle", 4)];
+        let state = from_basilk(basilk_state(), &config).unwrap();
+        let backlog = &state["projects"][0]["columns"]["Backlog"];
+        assert_eq!(backlog[0]["priority"], "Top");
+        assert_eq!(backlog[1]["priority"], "Bottom");
+
+        config.priorities.clear();
+        let state = from_basilk(basilk_state(), &config).unwrap();
+        assert!(state["projects"][0]["columns"]["Backlog"][0]["priority"].is_null());
+    }
+
+    #[test]
+    fn invalid_basilk_data_is_an_error() {
+        let config = Config::default();
+        let invalid_priority =
+            json!([{ "title": "P", "tasks": [{ "title": "T", "priority": 7, "status": "Done" }] }]);
+        let invalid_status =
+            json!([{ "title": "P", "tasks": [{ "title": "T", "priority": 0, "status": "?" }] }]);
+        let missing_title = json!([{ "tasks": [] }]);
+        for state in [invalid_priority, invalid_status, missing_title, json!({})] {
+            assert!(from_basilk(state, &config).is_err());
+        }
+    }
+
+    #[test]
+    fn ids_and_task_numbers_are_added() {
+        let state = from_basilk(basilk_state(), &Config::default()).unwrap();
+        let state = add_ids(state).unwrap();
+        let project = &state["projects"][0];
+        assert!(project["id"].is_string());
+        assert_eq!(project["last_task_number"], 3);
+
+        let tasks = project["columns"].as_object().unwrap().values();
+        let mut numbers: Vec<u64> = tasks
+            .flat_map(|tasks| tasks.as_array().unwrap())
+            .inspect(|task| assert!(task["id"].is_string()))
+            .map(|task| task["number"].as_u64().unwrap())
+            .collect();
+        numbers.sort();
+        assert_eq!(numbers, [1, 2, 3]);
+    }
+
+    #[test]
+    fn tasks_in_done_columns_are_completed() {
+        let config = Config::default();
+        let state = add_ids(from_basilk(basilk_state(), &config).unwrap()).unwrap();
+        let state = add_timestamps(state, &config).unwrap();
+        let columns = &state["projects"][0]["columns"];
+        assert!(columns["Done"][0]["completed_at"].is_string());
+        assert!(columns["Backlog"][0]["created_at"].is_string());
+        assert!(columns["Backlog"][0].get("completed_at").is_none());
+    }
+
+    #[test]
+    fn malformed_state_is_an_error() {
+        let config = Config::default();
+        assert!(add_ids(json!({ "projects": 1 })).is_err());
+        assert!(add_ids(json!({ "projects": [{ "title": "No columns" }] })).is_err());
+        assert!(
+            add_timestamps(
+                json!({ "projects": [{ "columns": { "A": [1] } }] }),
+                &config
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn basilk_state_migrates_to_current_version() {
+        let config = Config::default();
+        let state = State::migrate(State::BASILK_VERSION, basilk_state(), &config).unwrap();
+        let state: State = serde_json::from_value(state).unwrap();
+        let project = &state.projects()[0];
+        assert_eq!(project.tasks().count(), 3);
+        assert!(project.columns.get("Done")[0].completed_at().is_some());
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let error = State::migrate(State::CURRENT_VERSION + 1, json!({}), &Config::default());
+        assert!(error.is_err());
+    }
 }
@@ -0,0 +1,65 @@
+use std::ops::{Deref, Index};
+
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+
+use crate::Identifiable;
+
+/// A vector that keeps the items in the order they were added in
+#[derive(Derivative, Serialize, Deserialize, Clone, Debug)]
+#[derivative(Default(bound = ""))]
+pub struct OrderedVec<T>(Vec<T>);
+
+impl<T> Deref for OrderedVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T> OrderedVec<T> {
+    /// Appends the value and returns the index
+    pub fn push(&mut self, value: T) -> usize {
+        self.0.push(value);
+        self.0.len() - 1
+    }
+
+    pub fn insert(&mut self, idx: usize, value: T) { self.0.insert(idx, value) }
+    pub fn remove(&mut self, idx: usize) -> T { self.0.remove(idx) }
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> { self.0.iter_mut() }
+    pub fn modify_item_at<U>(&mut self, idx: usize, f: impl FnOnce(&mut T) -> U) -> U {
+        f(&mut self.0[idx])
+    }
+}
+
+impl<T: Identifiable> OrderedVec<T> {
+    pub fn position_by_id(&self, id: T::Id) -> Option<usize> {
+        self.iter().position(|item| item.id() == id)
+    }
+
+    pub fn get_by_id(&self, id: T::Id) -> Option<&T> { self.iter().find(|item| item.id() == id) }
+    pub fn remove_by_id(&mut self, id: T::Id) -> Option<T> {
+        Some(self.remove(self.position_by_id(id)?))
+    }
+
+    pub fn modify_item_with_id<U>(&mut self, id: T::Id, f: impl FnOnce(&mut T) -> U) -> Option<U> {
+        Some(self.modify_item_at(self.position_by_id(id)?, f))
+    }
+
+    /// Used to reorder items by hand
+    pub(crate) fn swap(&mut self, a: T::Id, b: T::Id) -> Option<()> {
+        let a = self.position_by_id(a)?;
+        let b = self.position_by_id(b)?;
+        self.0.swap(a, b);
+        Some(())
+    }
+}
+
+impl<T> FromIterator<T> for OrderedVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self { Self(Vec::from_iter(iter)) }
+}
+
+impl<T> Index<usize> for OrderedVec<T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &Self::Output { &self.0[idx] }
+}
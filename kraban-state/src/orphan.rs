@@ -0,0 +1,107 @@
+use itertools::Itertools;
+use kraban_config::{ColumnConfig, Config};
+
+use crate::{Identifiable, Project, ProjectId, State};
+
+/// Tasks of a project in a column that isn't in the config anymore, so they aren't shown anywhere
+pub struct OrphanedColumn<'a> {
+    pub project: &'a Project,
+    pub column: &'a str,
+    pub tasks: usize,
+    /// The user chose to keep the column as it is
+    pub hidden: bool
+}
+
+impl State {
+    /// Columns holding tasks that are missing from the config, including the hidden ones
+    pub fn orphaned_columns<'a>(&'a self, config: &Config) -> Vec<OrphanedColumn<'a>> {
+        let is_configured = |name: &str| config.column_configs().any(|column| column.name == name);
+        self.projects
+            .iter()
+            .flat_map(|project| {
+                project
+                    .columns
+                    .iter()
+                    .filter(|(name, tasks)| !tasks.is_empty() && !is_configured(name))
+                    .map(move |(name, tasks)| OrphanedColumn {
+                        project,
+                        column: name,
+                        tasks: tasks.len(),
+                        hidden: project.hidden_columns.contains(name)
+                    })
+                    .sorted_by_key(|orphan| orphan.column)
+            })
+            .collect()
+    }
+
+    /// The first orphaned column that isn't hidden in every project
+    pub fn unresolved_orphaned_column(&self, config: &Config) -> Option<&str> {
+        self.orphaned_columns(config)
+            .into_iter()
+            .find(|orphan| !orphan.hidden)
+            .map(|orphan| orphan.column)
+    }
+
+    /// Moves the tasks of the orphaned column to a configured one in every project, returns the description of the change
+    pub fn remap_column(&mut self, from: &str, to: &ColumnConfig, config: &Config) -> String {
+        for project in self.projects_with_column(from) {
+            let f = |project: &mut Project| {
+                let tasks = Vec::from_iter(project.columns.get(from).iter().map(Identifiable::id));
+                for task in tasks {
+                    project.move_task(task, from, to, config);
+                }
+
+                project.columns.remove(from);
+                project.hidden_columns.retain(|column| column != from);
+            };
+            self.projects_mut().modify_item_with_id(project, f);
+        }
+
+        format!("moved the tasks of column '{from}' to {}", to.name)
+    }
+
+    /// Keeps the orphaned column as it is without asking about it again
+    pub fn hide_column(&mut self, column: &str) -> String {
+        for project in self.projects_with_column(column) {
+            let f = |project: &mut Project| {
+                if !project.hidden_columns.iter().any(|hidden| hidden == column) {
+                    project.hidden_columns.push(column.to_string());
+                }
+            };
+            self.projects_mut().modify_item_with_id(project, f);
+        }
+
+        format!("hid column '{column}'")
+    }
+
+    /// Moves the tasks of the orphaned column to the trash in every project
+    pub fn trash_column(&mut self, column: &str) -> String {
+        for project in self.projects_with_column(column) {
+            let tasks = self
+                .project(project)
+                .map(|project| {
+                    Vec::from_iter(project.columns.get(column).iter().map(Identifiable::id))
+                })
+                .unwrap_or_default();
+            for task in tasks {
+                self.trash_task(project, column, task);
+            }
+
+            let f = |project: &mut Project| {
+                project.columns.remove(column);
+                project.hidden_columns.retain(|hidden| hidden != column);
+            };
+            self.projects_mut().modify_item_with_id(project, f);
+        }
+
+        format!("deleted the tasks of column '{column}'")
+    }
+
+    fn projects_with_column(&self, column: &str) -> Vec<ProjectId> {
+        self.projects
+            .iter()
+            .filter(|project| project.columns.contains_key(column))
+            .map(Project::id)
+            .collect()
+    }
+}
@@ -1,25 +1,23 @@
-use kraban_config::Config;
+use kraban_config::{Config, LevelConfig};
 use serde::{Deserialize, Serialize};
-use strum_macros::{EnumCount, EnumIter, IntoStaticStr};
 
-#[derive(
-    Clone,
-    Copy,
-    Debug,
-    Serialize,
-    Deserialize,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    EnumCount,
-    EnumIter,
-    IntoStaticStr,
-)]
-pub enum Priority {
-    Low,
-    Medium,
-    High
+/// The name of one of the priority levels in the config
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Priority(String);
+
+impl Priority {
+    pub fn new(name: String) -> Self { Self(name) }
+    pub fn name(&self) -> &str { &self.0 }
+    /// None if the level was removed from the config
+    pub fn level<'a>(&self, config: &'a Config) -> Option<&'a LevelConfig> {
+        config.priority_level(&self.0)
+    }
+
+    /// Levels missing from the config are weighted like no priority
+    pub fn weight(priority: Option<&Self>, config: &Config) -> Option<i32> {
+        Some(priority?.level(config)?.weight)
+    }
 }
 
 pub trait SetPriority {
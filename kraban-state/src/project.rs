@@ -1,24 +1,34 @@
+use chrono::Local;
 use derivative::Derivative;
+use hashbrown::HashMap;
 use kraban_config::{ColumnConfig, Config};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
 use super::Priority;
-use crate::{Columns, DueTask, SetPriority};
+use crate::{Change, Column, Columns, DueTask, Identifiable, ProjectId, SetPriority, Task, TaskId};
 
-#[derive(Derivative, Serialize, Deserialize, Default, Debug)]
-#[derivative(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Derivative, Serialize, Deserialize, Default, Clone, Debug)]
+#[derivative(PartialEq, Eq)]
 #[skip_serializing_none]
 pub struct Project {
-    // Priority should be on top so it's sorted properly
     pub priority: Option<Priority>,
-    #[derivative(PartialOrd = "ignore")]
-    #[derivative(Ord = "ignore")]
     pub title: String,
     #[derivative(PartialEq = "ignore")]
-    #[derivative(PartialOrd = "ignore")]
-    #[derivative(Ord = "ignore")]
-    pub columns: Columns
+    pub columns: Columns,
+    #[derivative(PartialEq = "ignore")]
+    id: ProjectId,
+    /// Used to give every task a short number unique within the project
+    #[derivative(PartialEq = "ignore")]
+    last_task_number: u32,
+    /// Archived projects are only shown in the archive
+    #[derivative(PartialEq = "ignore")]
+    #[serde(default)]
+    pub archived: bool,
+    /// Columns missing from the config that the user chose to leave alone, see [`crate::State::hide_column`]
+    #[derivative(PartialEq = "ignore")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) hidden_columns: Vec<String>
 }
 
 impl Project {
@@ -29,29 +39,190 @@ impl Project {
         }
     }
 
+    /// Moves the project to the archive, stopping its timers as its tasks can't be reached from there
+    pub fn archive(&mut self) {
+        self.archived = true;
+        self.stop_timers();
+    }
+
+    /// Adds a newly created task to the column, numbering it, and returns its index
+    pub fn add_task(&mut self, column: &str, mut task: Task) -> usize {
+        self.number_task(&mut task);
+        task.record(Change::EnteredColumn {
+            column: column.to_string()
+        });
+        self.columns.get_mut(column).push(task)
+    }
+
+    pub fn modify_task<T>(
+        &mut self,
+        column: &str,
+        task: TaskId,
+        f: impl FnOnce(&mut Task) -> T
+    ) -> Option<T> {
+        self.columns
+            .get_mut(column)
+            .modify_item_with_id(task, |task| {
+                let before = task.tracked();
+                let out = f(task);
+                task.updated_at = Local::now();
+                task.record_changes(before);
+                out
+            })
+    }
+
+    /// Moves the task to another column, marking it as completed and stopping its timer when it enters a done column.
+    /// Completing a recurring task spawns its next occurrence in the first column
+    pub fn move_task(
+        &mut self,
+        task: TaskId,
+        from: &str,
+        to: &ColumnConfig,
+        config: &Config
+    ) -> Option<&Task> {
+        let task = self.columns.get_mut(from).remove_by_id(task)?;
+        Some(self.receive_task(task, to, config))
+    }
+
+    /// Puts a task coming from another column or project in the column, see [`Project::move_task`]
+    pub(crate) fn receive_task(
+        &mut self,
+        mut task: Task,
+        to: &ColumnConfig,
+        config: &Config
+    ) -> &Task {
+        if to.done_column
+            && task.completed_at.is_none()
+            && let Some(next) = task.take_next_occurrence(config)
+            && let Some(first_column) = config.column_configs().next()
+        {
+            self.add_task(&first_column.name, next);
+        }
+
+        // Time is reported per column, so a running timer starts over in the new column
+        if task.timer_started_at().is_some() {
+            task.stop_timer();
+            if !to.done_column {
+                task.start_timer(&to.name);
+            }
+        }
+
+        let now = Local::now();
+        task.completed_at = to.done_column.then(|| task.completed_at.unwrap_or(now));
+        task.updated_at = now;
+        task.record(Change::EnteredColumn {
+            column: to.name.clone()
+        });
+
+        let column = self.columns.get_mut(&to.name);
+        let idx = column.push(task);
+        &column[idx]
+    }
+
+    /// Gives the task the next number of the project, numbers of tasks coming from other projects may be taken
+    pub(crate) fn number_task(&mut self, task: &mut Task) {
+        self.last_task_number += 1;
+        task.number = self.last_task_number;
+    }
+
+    /// Swaps two tasks of a column, used to reorder manually sorted columns
+    pub fn swap_tasks(&mut self, column: &str, a: TaskId, b: TaskId) -> Option<()> {
+        self.columns.get_mut(column).swap(a, b)
+    }
+
+    /// Whether the column holds more tasks than its WIP limit
+    pub fn is_over_wip_limit(&self, column: &ColumnConfig) -> bool {
+        column
+            .wip_limit
+            .is_some_and(|limit| self.columns.get(&column.name).len() > limit)
+    }
+
+    /// Whether adding a task to the column would take it over its WIP limit
+    pub fn is_at_wip_limit(&self, column: &ColumnConfig) -> bool {
+        column
+            .wip_limit
+            .is_some_and(|limit| self.columns.get(&column.name).len() >= limit)
+    }
+
+    /// A copy of the project with copies of its tasks, see [`Task::duplicate`]. Tasks in done columns
+    /// keep their history, see [`Task::duplicate_done`]. Blockers among the tasks point to the copies
+    pub(crate) fn duplicate(&self, config: &Config) -> Project {
+        let mut copy = Project {
+            id: ProjectId::new(),
+            title: format!("{} (copy)", self.title),
+            columns: Columns::default(),
+            last_task_number: 0,
+            archived: false,
+            ..self.clone()
+        };
+
+        let mut tasks = Vec::from_iter(
+            self.columns
+                .iter()
+                .flat_map(|(column, tasks)| tasks.iter().map(move |task| (column, task)))
+        );
+        tasks.sort_by_key(|(_, task)| task.number);
+
+        let mut copies = HashMap::new();
+        for (column, task) in tasks {
+            let done = config.is_done_column(column);
+            let mut task_copy = match done {
+                true => task.duplicate_done(),
+                false => task.duplicate()
+            };
+            copies.insert(task.id(), task_copy.id());
+            match done {
+                // Adding it would record it entering the column today
+                true => {
+                    copy.number_task(&mut task_copy);
+                    copy.columns.get_mut(column).push(task_copy);
+                }
+                false => {
+                    copy.add_task(column, task_copy);
+                }
+            }
+        }
+
+        for task in copy.columns.values_mut().flat_map(Column::iter_mut) {
+            for blocker in &mut task.blocked_by {
+                if let Some(blocker_copy) = copies.get(blocker) {
+                    *blocker = *blocker_copy;
+                }
+            }
+        }
+
+        copy
+    }
+
+    /// Every task of the project in no particular order
+    pub fn tasks(&self) -> impl Iterator<Item = &Task> {
+        self.columns.values().flat_map(|column| column.iter())
+    }
+
     pub(crate) fn due_tasks_by_column<'a>(
         &'a self,
-        column_config: &'a ColumnConfig,
-        idx_of_self: usize
+        column_config: &'a ColumnConfig
     ) -> impl Iterator<Item = DueTask<'a>> {
         self.columns
             .get(&column_config.name)
             .iter()
-            .enumerate()
-            .filter_map(|(i, task)| Some((i, task, task.due_date()?)))
-            .map(move |(idx, task, due_date)| DueTask {
+            .filter_map(|task| Some((task, task.due_date()?)))
+            .map(move |(task, due_date)| DueTask {
                 project: self,
                 column_config,
-                idx,
-                project_idx: idx_of_self,
+                task,
                 priority: task.priority(),
                 due_date,
-                difficulty: task.difficulty,
-                title: &task.title
+                difficulty: task.difficulty.as_ref()
             })
     }
 }
 
+impl Identifiable for Project {
+    type Id = ProjectId;
+    fn id(&self) -> ProjectId { self.id }
+}
+
 impl SetPriority for Project {
     fn set_priority(&mut self, priority: Option<Priority>, _: &Config) { self.priority = priority }
 }
@@ -0,0 +1,132 @@
+use std::fmt::{self, Display};
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use time::{Date, Month};
+
+/// How often a task repeats, see [`crate::Project::move_task`]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    Days(u32),
+    Weeks(u32),
+    Months(u32),
+    Weekdays(Vec<Weekday>)
+}
+
+impl Recurrence {
+    /// Parses rules such as `3d`, `2w`, `1m` or `mon,thu`
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        let interval = |suffix| {
+            text.strip_suffix(suffix)?
+                .parse()
+                .ok()
+                .filter(|interval| *interval != 0)
+        };
+
+        if let Some(days) = interval('d') {
+            return Some(Self::Days(days));
+        }
+        if let Some(weeks) = interval('w') {
+            return Some(Self::Weeks(weeks));
+        }
+        if let Some(months) = interval('m') {
+            return Some(Self::Months(months));
+        }
+
+        let weekdays: Vec<Weekday> = text
+            .split(',')
+            .map(|weekday| weekday.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        Some(Self::Weekdays(weekdays))
+    }
+
+    /// The first date after `from` on which the task recurs
+    pub fn next(&self, from: Date) -> Option<Date> {
+        // Plain dates, a date with a time zone can land on another day
+        let from = NaiveDate::from_ymd_opt(from.year(), from.month() as u32, from.day().into())?;
+        let next = match self {
+            Recurrence::Days(days) => from.checked_add_days(Days::new(*days as u64)),
+            Recurrence::Weeks(weeks) => from.checked_add_days(Days::new(*weeks as u64 * 7)),
+            Recurrence::Months(months) => from.checked_add_months(Months::new(*months)),
+            Recurrence::Weekdays(weekdays) => (1..=7)
+                .filter_map(|days| from.checked_add_days(Days::new(days)))
+                .find(|date| weekdays.contains(&date.weekday()))
+        };
+
+        let next = next?;
+        Date::from_calendar_date(
+            next.year(),
+            Month::try_from(next.month() as u8).ok()?,
+            next.day() as u8
+        )
+        .ok()
+    }
+}
+
+impl Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recurrence::Days(days) => write!(f, "{days}d"),
+            Recurrence::Weeks(weeks) => write!(f, "{weeks}w"),
+            Recurrence::Months(months) => write!(f, "{months}m"),
+            Recurrence::Weekdays(weekdays) => {
+                let weekdays = weekdays
+                    .iter()
+                    .map(|weekday| weekday.to_string().to_lowercase());
+                write!(f, "{}", Vec::from_iter(weekdays).join(","))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+    use time::{Date, Month};
+
+    use super::Recurrence;
+
+    fn date(year: i32, month: Month, day: u8) -> Date {
+        Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn recurs_within_a_thirty_day_month() {
+        let from = date(2026, Month::April, 10);
+        assert_eq!(
+            Recurrence::Days(3).next(from),
+            Some(date(2026, Month::April, 13))
+        );
+        assert_eq!(
+            Recurrence::Weeks(3).next(from),
+            Some(date(2026, Month::May, 1))
+        );
+        assert_eq!(
+            Recurrence::Months(1).next(date(2026, Month::March, 31)),
+            Some(date(2026, Month::April, 30))
+        );
+    }
+
+    #[test]
+    fn recurs_within_february() {
+        let from = date(2026, Month::February, 10);
+        assert_eq!(
+            Recurrence::Weeks(1).next(from),
+            Some(date(2026, Month::February, 17))
+        );
+        assert_eq!(
+            Recurrence::Days(20).next(from),
+            Some(date(2026, Month::March, 2))
+        );
+        assert_eq!(
+            Recurrence::Months(1).next(date(2026, Month::January, 31)),
+            Some(date(2026, Month::February, 28))
+        );
+        assert_eq!(
+            Recurrence::Weekdays(vec![Weekday::Mon]).next(from),
+            Some(date(2026, Month::February, 16))
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf}
+};
+
+use color_eyre::{Result, eyre::Context};
+use kraban_lib::write_atomically;
+use serde_json::Value;
+
+use super::Storage;
+
+/// The whole state in a single json file, rewritten on every save
+pub struct JsonStorage {
+    path: PathBuf
+}
+
+impl JsonStorage {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            path: dir.join("tasks.json")
+        }
+    }
+}
+
+impl Storage for JsonStorage {
+    fn read(&mut self) -> Result<Option<Value>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            error => error?
+        };
+
+        let value: Value =
+            serde_json::from_str(&contents).wrap_err("The state file is not valid json")?;
+        Ok((!value.is_null()).then_some(value))
+    }
+
+    fn write(&mut self, document: &Value) -> Result<()> {
+        let contents = serde_json::to_string(document)?;
+        write_atomically(&self.path, contents.as_bytes())
+    }
+}
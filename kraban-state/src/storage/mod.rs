@@ -0,0 +1,50 @@
+mod json;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+#[cfg(not(feature = "sqlite"))]
+use color_eyre::eyre::bail;
+use color_eyre::{Result, eyre::OptionExt};
+pub use json::JsonStorage;
+use kraban_config::{Config, StorageBackend};
+use serde_json::Value;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStorage;
+
+use crate::State;
+
+/// Where the state is loaded from and saved to. Every backend stores the same versioned json
+/// document, so migrations and backups don't need to know which backend is used
+pub trait Storage {
+    /// Returns `None` if nothing has been saved yet
+    fn read(&mut self) -> Result<Option<Value>>;
+    fn write(&mut self, document: &Value) -> Result<()>;
+}
+
+/// Opens the backend selected in the config
+pub fn open_storage(config: &Config) -> Result<Box<dyn Storage>> { open(config.storage, config) }
+
+/// Opens the backend in the state dir of the board
+pub fn open(backend: StorageBackend, config: &Config) -> Result<Box<dyn Storage>> {
+    let dir = config.state_dir()?;
+    let storage: Box<dyn Storage> = match backend {
+        StorageBackend::Json => Box::new(JsonStorage::new(&dir)),
+        #[cfg(feature = "sqlite")]
+        StorageBackend::Sqlite => Box::new(SqliteStorage::open(&dir)?),
+        #[cfg(not(feature = "sqlite"))]
+        StorageBackend::Sqlite => {
+            bail!("This build of kraban doesn't support sqlite, rebuild it with the sqlite feature")
+        }
+    };
+
+    Ok(storage)
+}
+
+impl State {
+    /// Copies the state from one backend to another, migrating it to the current version on the way
+    pub fn convert(from: &mut dyn Storage, to: &mut dyn Storage, config: &Config) -> Result<()> {
+        let document = from.read()?.ok_or_eyre("There is no state to convert")?;
+        let state = Self::from_document(document, config)?;
+        to.write(&state.to_document())
+    }
+}
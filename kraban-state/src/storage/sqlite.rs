@@ -0,0 +1,497 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path
+};
+
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, bail}
+};
+use rusqlite::{Connection, OptionalExtension, Transaction, params};
+use serde_json::{Map, Value, json};
+use time::Date;
+
+use super::Storage;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value);
+CREATE TABLE IF NOT EXISTS projects (
+    id TEXT PRIMARY KEY,
+    position INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    archived INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tasks (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    column_name TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    number INTEGER,
+    title TEXT NOT NULL,
+    priority TEXT,
+    difficulty TEXT,
+    due_date TEXT,
+    created_at TEXT,
+    updated_at TEXT,
+    completed_at TEXT,
+    data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS trash (
+    id TEXT PRIMARY KEY,
+    position INTEGER NOT NULL,
+    deleted_at TEXT,
+    data TEXT NOT NULL
+);
+";
+
+/// A row per project, task and trash item, the rest of the state is kept as json in `meta`.
+/// Only the rows that changed since the last read or write are written, the columns next to
+/// `data` are copies for querying the tasks with sql
+pub struct SqliteStorage {
+    connection: Connection,
+    rows: Rows
+}
+
+#[derive(Default)]
+struct Rows {
+    /// The project json without its columns
+    projects: HashMap<String, ProjectRow>,
+    tasks: HashMap<String, TaskRow>,
+    trash: HashMap<String, TrashRow>,
+    /// The fields of the state that don't have a table, like the templates
+    state: Map<String, Value>
+}
+
+#[derive(PartialEq)]
+struct ProjectRow {
+    position: usize,
+    data: Value
+}
+
+#[derive(PartialEq)]
+struct TaskRow {
+    project: String,
+    column: String,
+    position: usize,
+    data: Value
+}
+
+#[derive(PartialEq)]
+struct TrashRow {
+    position: usize,
+    data: Value
+}
+
+impl SqliteStorage {
+    pub fn open(dir: &Path) -> Result<Self> {
+        let connection = Connection::open(dir.join("tasks.sqlite3"))?;
+        connection.execute_batch(SCHEMA)?;
+        let mut storage = Self {
+            connection,
+            rows: Rows::default()
+        };
+        storage.load()?;
+        Ok(storage)
+    }
+
+    /// Reads every row into the cache and returns the stored version
+    fn load(&mut self) -> Result<Option<u64>> {
+        let version = self
+            .connection
+            .query_row("SELECT value FROM meta WHERE key = 'version'", [], |row| {
+                row.get(0)
+            })
+            .optional()?;
+
+        let mut rows = Rows::default();
+        let state: Option<String> = self
+            .connection
+            .query_row("SELECT value FROM meta WHERE key = 'state'", [], |row| {
+                row.get(0)
+            })
+            .optional()?;
+        if let Some(state) = state {
+            rows.state = serde_json::from_str(&state)?;
+        }
+
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, position, data FROM projects")?;
+        for row in statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))? {
+            let (id, position, data): (String, usize, String) = row?;
+            let data = serde_json::from_str(&data)?;
+            rows.projects.insert(id, ProjectRow { position, data });
+        }
+
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, project_id, column_name, position, data FROM tasks")?;
+        for row in statement.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?
+            ))
+        })? {
+            let (id, project, column, position, data): (String, String, String, usize, String) =
+                row?;
+            let data = serde_json::from_str(&data)?;
+            rows.tasks.insert(
+                id,
+                TaskRow {
+                    project,
+                    column,
+                    position,
+                    data
+                }
+            );
+        }
+
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, position, data FROM trash")?;
+        for row in statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))? {
+            let (id, position, data): (String, usize, String) = row?;
+            let data = serde_json::from_str(&data)?;
+            rows.trash.insert(id, TrashRow { position, data });
+        }
+
+        self.rows = rows;
+        Ok(version)
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn read(&mut self) -> Result<Option<Value>> {
+        let Some(version) = self.load()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(self.rows.to_document(version)?))
+    }
+
+    fn write(&mut self, document: &Value) -> Result<()> {
+        let version = document["version"]
+            .as_u64()
+            .ok_or_eyre("Only versioned state can be stored in sqlite")?;
+        let rows = Rows::from_document(document)?;
+
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('version', ?1)",
+            [version]
+        )?;
+        if self.rows.state != rows.state {
+            transaction.execute(
+                "INSERT OR REPLACE INTO meta (key, value) VALUES ('state', ?1)",
+                [Value::Object(rows.state.clone()).to_string()]
+            )?;
+        }
+
+        sync(
+            &transaction,
+            "projects",
+            &self.rows.projects,
+            &rows.projects,
+            |id, row| {
+                transaction.execute(
+                    "INSERT OR REPLACE INTO projects (id, position, title, archived, data)
+                    VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        id,
+                        row.position,
+                        row.data["title"].as_str(),
+                        row.data["archived"].as_bool().unwrap_or_default(),
+                        row.data.to_string()
+                    ]
+                )
+            }
+        )?;
+
+        sync(
+            &transaction,
+            "tasks",
+            &self.rows.tasks,
+            &rows.tasks,
+            |id, row| {
+                let due_date = serde_json::from_value::<Option<Date>>(row.data["due_date"].clone())
+                    .ok()
+                    .flatten()
+                    .map(|due_date| due_date.to_string());
+                transaction.execute(
+                    "INSERT OR REPLACE INTO tasks (id, project_id, column_name, position, number,
+                    title, priority, difficulty, due_date, created_at, updated_at, completed_at, data)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                    params![
+                        id,
+                        row.project,
+                        row.column,
+                        row.position,
+                        row.data["number"].as_u64(),
+                        row.data["title"].as_str(),
+                        row.data["priority"].as_str(),
+                        row.data["difficulty"].as_str(),
+                        due_date,
+                        row.data["created_at"].as_str(),
+                        row.data["updated_at"].as_str(),
+                        row.data["completed_at"].as_str(),
+                        row.data.to_string()
+                    ]
+                )
+            }
+        )?;
+
+        sync(
+            &transaction,
+            "trash",
+            &self.rows.trash,
+            &rows.trash,
+            |id, row| {
+                transaction.execute(
+                    "INSERT OR REPLACE INTO trash (id, position, deleted_at, data)
+                    VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        id,
+                        row.position,
+                        row.data["deleted_at"].as_str(),
+                        row.data.to_string()
+                    ]
+                )
+            }
+        )?;
+
+        transaction.commit()?;
+        self.rows = rows;
+        Ok(())
+    }
+}
+
+/// Writes the rows that are new or changed and deletes the ones that are gone
+fn sync<R: PartialEq>(
+    transaction: &Transaction,
+    table: &str,
+    old: &HashMap<String, R>,
+    new: &HashMap<String, R>,
+    upsert: impl Fn(&str, &R) -> rusqlite::Result<usize>
+) -> Result<()> {
+    for (id, row) in new {
+        if old.get(id) != Some(row) {
+            upsert(id, row)?;
+        }
+    }
+
+    let mut delete = transaction.prepare(&format!("DELETE FROM {table} WHERE id = ?1"))?;
+    for id in old.keys().filter(|id| !new.contains_key(*id)) {
+        delete.execute([id])?;
+    }
+
+    Ok(())
+}
+
+impl Rows {
+    fn from_document(document: &Value) -> Result<Self> {
+        let mut state = document["state"]
+            .as_object()
+            .ok_or_eyre("The state has to be an object")?
+            .clone();
+        let projects = state.remove("projects").unwrap_or_default();
+        let trash = state.remove("trash").unwrap_or_default();
+        let mut rows = Rows {
+            state,
+            ..Rows::default()
+        };
+        for (position, project) in items(&projects).enumerate() {
+            let project_id = id(project)?;
+            let mut data = project.clone();
+            let columns = data
+                .as_object_mut()
+                .ok_or_eyre("Projects have to be objects")?
+                .remove("columns");
+
+            for (column, tasks) in columns.iter().flat_map(Value::as_object).flatten() {
+                for (position, task) in items(tasks).enumerate() {
+                    rows.tasks.insert(
+                        id(task)?,
+                        TaskRow {
+                            project: project_id.clone(),
+                            column: column.clone(),
+                            position,
+                            data: task.clone()
+                        }
+                    );
+                }
+            }
+
+            rows.projects
+                .insert(project_id, ProjectRow { position, data });
+        }
+
+        for (position, item) in items(&trash).enumerate() {
+            rows.trash.insert(
+                id(item)?,
+                TrashRow {
+                    position,
+                    data: item.clone()
+                }
+            );
+        }
+
+        Ok(rows)
+    }
+
+    fn to_document(&self, version: u64) -> Result<Value> {
+        let mut columns: HashMap<&str, BTreeMap<&str, Vec<&Value>>> = HashMap::new();
+        let mut tasks = Vec::from_iter(&self.tasks);
+        tasks.sort_by_key(|(_, row)| row.position);
+        for (id, row) in tasks {
+            if !self.projects.contains_key(&row.project) {
+                bail!(
+                    "Task {id} belongs to project {} which doesn't exist",
+                    row.project
+                )
+            }
+
+            columns
+                .entry(&row.project)
+                .or_default()
+                .entry(&row.column)
+                .or_default()
+                .push(&row.data);
+        }
+
+        let mut projects = Vec::from_iter(&self.projects);
+        projects.sort_by_key(|(_, row)| row.position);
+        let projects = projects.into_iter().map(|(id, row)| {
+            let mut project = row.data.clone();
+            project["columns"] = json!(columns.remove(id.as_str()).unwrap_or_default());
+            project
+        });
+
+        let mut trash = Vec::from_iter(self.trash.values());
+        trash.sort_by_key(|row| row.position);
+        let mut state = self.state.clone();
+        state.insert("projects".to_string(), json!(Vec::from_iter(projects)));
+        state.insert(
+            "trash".to_string(),
+            json!(Vec::from_iter(trash.into_iter().map(|row| &row.data)))
+        );
+        Ok(json!({
+            "version": version,
+            "state": state
+        }))
+    }
+}
+
+/// The elements of a json array, or nothing if it's missing
+fn items(value: &Value) -> impl Iterator<Item = &Value> { value.as_array().into_iter().flatten() }
+
+fn id(value: &Value) -> Result<String> {
+    value["id"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_eyre("Everything stored in sqlite needs an id, migrate the state first")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use kraban_config::Config;
+    use serde_json::{Value, json};
+    use uuid::Uuid;
+
+    use super::SqliteStorage;
+    use crate::{
+        Identifiable, Project, State, Task,
+        storage::{JsonStorage, Storage}
+    };
+
+    fn temp_dir() -> PathBuf {
+        let dir = env::temp_dir().join(format!("kraban-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn document() -> Value {
+        json!({
+            "version": 3,
+            "state": {
+                "projects": [
+                    {
+                        "id": "p1",
+                        "title": "First",
+                        "columns": {
+                            "Backlog": [{ "id": "t1", "title": "One" }, { "id": "t2", "title": "Two" }],
+                            "Done": [{ "id": "t3", "title": "Three", "completed_at": "2026-10-01T12:00:00Z" }]
+                        }
+                    },
+                    { "id": "p2", "title": "Second", "columns": {} }
+                ],
+                "trash": [{ "id": "t4", "title": "Four", "deleted_at": "2026-10-02T12:00:00Z" }],
+                "templates": [{ "name": "Release", "tasks": [{ "column": "Backlog", "title": "Tag" }] }],
+                "added_by_a_later_version": { "answer": 42 }
+            }
+        })
+    }
+
+    #[test]
+    fn json_round_trips_through_sqlite() {
+        let dir = temp_dir();
+        let mut json = JsonStorage::new(&dir);
+        json.write(&document()).unwrap();
+
+        let document = json.read().unwrap().unwrap();
+        SqliteStorage::open(&dir).unwrap().write(&document).unwrap();
+        let document = SqliteStorage::open(&dir).unwrap().read().unwrap().unwrap();
+        json.write(&document).unwrap();
+
+        assert_eq!(json.read().unwrap(), Some(self::document()));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn changes_to_the_rest_of_the_state_are_written() {
+        let dir = temp_dir();
+        let mut sqlite = SqliteStorage::open(&dir).unwrap();
+        sqlite.write(&document()).unwrap();
+
+        let mut changed = document();
+        changed["state"]["templates"] = json!([]);
+        changed["state"]["added_by_a_later_version"]["answer"] = json!(43);
+        sqlite.write(&changed).unwrap();
+
+        let read = SqliteStorage::open(&dir).unwrap().read().unwrap();
+        assert_eq!(read, Some(changed));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn templates_are_saved_loaded_and_converted() {
+        let dir = temp_dir();
+        let config = Config::default();
+        let mut state = State::default();
+        let mut project = Project::new("Release".to_string());
+        project.add_task("Backlog", Task::new("Tag".to_string()));
+        let id = project.id();
+        state.projects_mut().push(project);
+        state.save_template(id, "Release".to_string(), &config);
+
+        let mut sqlite = SqliteStorage::open(&dir).unwrap();
+        sqlite.write(&state.to_document()).unwrap();
+        let loaded = State::new(&mut SqliteStorage::open(&dir).unwrap(), &config).unwrap();
+        let [template] = loaded.templates() else {
+            panic!("Expected a single template")
+        };
+        assert_eq!(template.name, "Release");
+        assert_eq!(template.tasks[0].title, "Tag");
+
+        let mut json = JsonStorage::new(&dir);
+        State::convert(&mut sqlite, &mut json, &config).unwrap();
+        let converted = State::new(&mut json, &config).unwrap();
+        assert_eq!(converted.templates().len(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,14 +1,12 @@
-use std::cmp::Ordering;
-
 use chrono::{Days, Local};
 use kraban_config::Config;
-use kraban_lib::chrono_date_to_time_date;
+use kraban_lib::{Timestamp, chrono_date_to_time_date, now};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use time::Date;
 
 use super::{Difficulty, Priority};
-use crate::priority::SetPriority;
+use crate::{Activity, Identifiable, Recurrence, TaskId, WorkInterval, priority::SetPriority};
 
 #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone, Debug)]
 #[skip_serializing_none]
@@ -17,51 +15,138 @@ pub struct Task {
     due_date: Option<Date>,
     pub difficulty: Option<Difficulty>,
     pub title: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<ChecklistItem>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Tasks that have to be done before this one, they can be in other projects
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<TaskId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>,
+    /// Tracked with [`crate::State::toggle_timer`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) work: Vec<WorkInterval>,
+    /// Appended to by [`crate::Project`] whenever the task is added, moved or modified
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) activity: Vec<Activity>,
     #[serde(default)]
-    due_date_manually_set: bool
+    due_date_manually_set: bool,
+    id: TaskId,
+    /// Short number unique within the project, assigned by [`crate::Project::add_task`]
+    pub(crate) number: u32,
+    created_at: Timestamp,
+    /// Updated by [`crate::Project::modify_task`] and [`crate::Project::move_task`]
+    pub(crate) updated_at: Timestamp,
+    /// Set when the task enters a done column and cleared when it leaves
+    pub(crate) completed_at: Option<Timestamp>
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct ChecklistItem {
+    pub title: String,
+    pub done: bool
+}
+
+impl ChecklistItem {
+    pub fn new(title: String) -> Self { Self { title, done: false } }
 }
 
 impl Task {
     pub fn new(title: String) -> Self {
+        let now = Local::now();
         Self {
             title,
+            created_at: now,
+            updated_at: now,
             ..Self::default()
         }
     }
 
-    pub fn priority(&self) -> Option<Priority> { self.priority }
+    pub fn number(&self) -> u32 { self.number }
+    pub fn created_at(&self) -> Timestamp { self.created_at }
+    pub fn updated_at(&self) -> Timestamp { self.updated_at }
+    pub fn completed_at(&self) -> Option<Timestamp> { self.completed_at }
+    pub fn priority(&self) -> Option<&Priority> { self.priority.as_ref() }
+    /// Returns how many checklist items are done and how many there are, if there are any
+    pub fn checklist_progress(&self) -> Option<(usize, usize)> {
+        let done = self.checklist.iter().filter(|item| item.done).count();
+        (!self.checklist.is_empty()).then_some((done, self.checklist.len()))
+    }
+
+    /// Whether the task has all of the given tags
+    pub fn has_tags(&self, tags: &[String]) -> bool {
+        tags.iter().all(|tag| self.tags.contains(tag))
+    }
+
     pub fn due_date(&self) -> Option<Date> { self.due_date }
     pub fn set_due_date(&mut self, due_date: Option<Date>) {
         self.due_date = due_date;
         self.due_date_manually_set = true;
     }
+
+    /// A new task with the same fields, its time tracking and history start over
+    pub(crate) fn duplicate(&self) -> Task {
+        let now = Local::now();
+        Task {
+            id: TaskId::new(),
+            work: Vec::new(),
+            activity: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+            ..self.clone()
+        }
+    }
+
+    /// A copy of a done task that keeps when it was created, worked through and completed, so that it
+    /// counts like the original in the flow report. Its time tracking isn't copied
+    pub(crate) fn duplicate_done(&self) -> Task {
+        Task {
+            id: TaskId::new(),
+            work: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Takes the recurrence away from the task and returns a fresh copy due on the next occurrence.
+    /// Tasks without a due date recur from today
+    pub(crate) fn take_next_occurrence(&mut self, config: &Config) -> Option<Task> {
+        let recurrence = self.recurrence.take()?;
+        let due_date = recurrence.next(self.due_date.unwrap_or_else(now))?;
+        let mut task = Task::new(self.title.clone());
+        task.set_priority(self.priority.clone(), config);
+        task.set_due_date(Some(due_date));
+        task.difficulty = self.difficulty.clone();
+        task.description = self.description.clone();
+        task.checklist = Vec::from_iter(
+            self.checklist
+                .iter()
+                .map(|item| ChecklistItem::new(item.title.clone()))
+        );
+        task.tags = self.tags.clone();
+        task.recurrence = Some(recurrence);
+        Some(task)
+    }
+}
+
+impl Identifiable for Task {
+    type Id = TaskId;
+    fn id(&self) -> TaskId { self.id }
 }
 
 impl SetPriority for Task {
     fn set_priority(&mut self, priority: Option<Priority>, config: &Config) {
-        self.priority = priority;
         if !self.due_date_manually_set {
             self.due_date = priority
-                .map(|priority| match priority {
-                    Priority::Low => config.default_due_dates.low,
-                    Priority::Medium => config.default_due_dates.medium,
-                    Priority::High => config.default_due_dates.high
-                } as u64)
-                .map(Days::new)
+                .as_ref()
+                .and_then(|priority| priority.level(config)?.due_days)
+                .map(|days| Days::new(days as u64))
                 .and_then(|days| Local::now().checked_add_days(days))
                 .map(chrono_date_to_time_date)
         }
+        self.priority = priority;
     }
 }
-
-impl Ord for Task {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.priority
-            .cmp(&other.priority)
-            .then(self.difficulty.cmp(&other.difficulty))
-    }
-}
-
-impl PartialOrd for Task {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
-}
@@ -0,0 +1,170 @@
+use kraban_config::Config;
+use kraban_lib::now;
+use serde::{Deserialize, Serialize};
+use time::Duration;
+
+use crate::{
+    ChecklistItem, Difficulty, Priority, Project, ProjectId, Recurrence, SetPriority, State, Task
+};
+
+/// The skeleton of a project that similar projects can be started from, see [`State::save_template`]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Template {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    pub tasks: Vec<TemplateTask>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TemplateTask {
+    pub column: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<Difficulty>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>,
+    /// Days between the day the template was saved and the due date, projects created from the template are due as many days after the day they are created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_in_days: Option<i64>
+}
+
+impl Template {
+    /// Tasks that were done are put back in the first column, as a new project hasn't done them yet.
+    /// Blockers aren't kept
+    fn new(name: String, project: &Project, config: &Config) -> Self {
+        let first_column = config.column_configs().next();
+        let column = |name: &str| match first_column {
+            Some(first_column) if config.is_done_column(name) => first_column.name.clone(),
+            _ => name.to_string()
+        };
+
+        let mut tasks = Vec::from_iter(
+            project
+                .columns
+                .iter()
+                .flat_map(|(name, tasks)| tasks.iter().map(move |task| (name, task)))
+        );
+        tasks.sort_by_key(|(_, task)| task.number());
+
+        let today = now();
+        Self {
+            name,
+            priority: project.priority.clone(),
+            tasks: Vec::from_iter(tasks.into_iter().map(|(name, task)| {
+                TemplateTask {
+                    column: column(name),
+                    title: task.title.clone(),
+                    priority: task.priority().cloned(),
+                    difficulty: task.difficulty.clone(),
+                    description: task.description.clone(),
+                    checklist: Vec::from_iter(task.checklist.iter().map(|item| item.title.clone())),
+                    tags: task.tags.clone(),
+                    recurrence: task.recurrence.clone(),
+                    due_in_days: task
+                        .due_date()
+                        .map(|due_date| (due_date - today).whole_days())
+                }
+            }))
+        }
+    }
+
+    /// A new project with the tasks of the template, due relative to today
+    fn instantiate(&self, title: String, config: &Config) -> Project {
+        let today = now();
+        let mut project = Project::new(title);
+        project.priority = self.priority.clone();
+        for template_task in &self.tasks {
+            let mut task = Task::new(template_task.title.clone());
+            task.set_priority(template_task.priority.clone(), config);
+            task.difficulty = template_task.difficulty.clone();
+            task.description = template_task.description.clone();
+            task.checklist = Vec::from_iter(
+                template_task
+                    .checklist
+                    .iter()
+                    .map(|title| ChecklistItem::new(title.clone()))
+            );
+            task.tags = template_task.tags.clone();
+            task.recurrence = template_task.recurrence.clone();
+            if let Some(days) = template_task.due_in_days {
+                task.set_due_date(today.checked_add(Duration::days(days)));
+            }
+
+            project.add_task(&template_task.column, task);
+        }
+
+        project
+    }
+}
+
+impl State {
+    pub fn templates(&self) -> &[Template] { &self.templates }
+
+    /// Saves the project as a template, replacing the template with the same name.
+    /// Templates aren't part of the undo history, returns the description of the change
+    pub fn save_template(
+        &mut self,
+        project: ProjectId,
+        name: String,
+        config: &Config
+    ) -> Option<String> {
+        let template = Template::new(name, self.project(project)?, config);
+        let description = match self
+            .templates
+            .iter_mut()
+            .find(|other| other.name == template.name)
+        {
+            Some(other) => {
+                *other = template;
+                format!("replaced template '{}'", other.name)
+            }
+            None => {
+                let description = format!("saved template '{}'", template.name);
+                self.templates.push(template);
+                description
+            }
+        };
+
+        self.describe_saved_change(description.clone());
+        Some(description)
+    }
+
+    /// Returns the description of the change
+    pub fn delete_template(&mut self, name: &str) -> Option<String> {
+        let idx = self
+            .templates
+            .iter()
+            .position(|template| template.name == name)?;
+        let template = self.templates.remove(idx);
+        let description = format!("deleted template '{}'", template.name);
+        self.describe_saved_change(description.clone());
+        Some(description)
+    }
+
+    /// Returns the description of the change
+    pub fn create_project_from_template(
+        &mut self,
+        template: &str,
+        title: String,
+        config: &Config
+    ) -> Option<String> {
+        let template = self.templates.iter().find(|other| other.name == template)?;
+        let project = template.instantiate(title, config);
+        let description = format!(
+            "created project '{}' from template '{}'",
+            project.title, template.name
+        );
+
+        self.projects_mut().push(project);
+        Some(description)
+    }
+}
@@ -0,0 +1,168 @@
+use chrono::{Local, TimeDelta};
+use kraban_config::Config;
+use kraban_lib::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::{Identifiable, Project, ProjectId, State, Task, TaskId};
+
+/// A period of work on a task, it's still going on if it has no end
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct WorkInterval {
+    pub start: Timestamp,
+    pub end: Option<Timestamp>,
+    /// The column the task was in, intervals tracked before it was recorded count for the
+    /// column the task is in now
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>
+}
+
+impl WorkInterval {
+    /// The part of the interval after `since`, running intervals count until now
+    fn duration(&self, since: Option<Timestamp>, now: Timestamp) -> TimeDelta {
+        let start = since.map_or(self.start, |since| self.start.max(since));
+        (self.end.unwrap_or(now) - start).max(TimeDelta::zero())
+    }
+}
+
+impl Task {
+    pub fn work(&self) -> &[WorkInterval] { &self.work }
+    pub fn timer_started_at(&self) -> Option<Timestamp> {
+        self.work
+            .last()
+            .filter(|interval| interval.end.is_none())
+            .map(|interval| interval.start)
+    }
+
+    /// Time worked on the task since the given time, or in total
+    pub fn tracked_time(&self, since: Option<Timestamp>) -> TimeDelta {
+        let now = Local::now();
+        self.work
+            .iter()
+            .map(|interval| interval.duration(since, now))
+            .sum()
+    }
+
+    pub(crate) fn start_timer(&mut self, column: &str) {
+        if self.timer_started_at().is_none() {
+            self.work.push(WorkInterval {
+                start: Local::now(),
+                end: None,
+                column: Some(column.to_string())
+            });
+        }
+    }
+
+    pub(crate) fn stop_timer(&mut self) {
+        if let Some(interval) = self.work.last_mut()
+            && interval.end.is_none()
+        {
+            interval.end = Some(Local::now());
+        }
+    }
+}
+
+impl Project {
+    pub(crate) fn stop_timers(&mut self) {
+        self.columns
+            .values_mut()
+            .flat_map(|column| column.iter_mut())
+            .for_each(Task::stop_timer);
+    }
+}
+
+/// Time tracked in a project, per column it was tracked in, in the order of the config
+pub struct ProjectTime<'a> {
+    pub project: &'a Project,
+    pub columns: Vec<(&'a str, TimeDelta)>
+}
+
+impl State {
+    /// The task whose timer is running, there is at most one
+    pub fn running_timer(&self) -> Option<(&Project, &Task)> {
+        self.projects.iter().find_map(|project| {
+            let task = project
+                .tasks()
+                .find(|task| task.timer_started_at().is_some())?;
+            Some((project, task))
+        })
+    }
+
+    /// Starts the timer of the task, stopping the one that is running, or stops it if it's the one running.
+    /// Returns the description of the change
+    pub fn toggle_timer(
+        &mut self,
+        project: ProjectId,
+        column: &str,
+        task: TaskId
+    ) -> Option<String> {
+        let running = self.running_timer().map(|(project, task)| {
+            let column = project
+                .columns
+                .iter()
+                .find(|(_, column)| column.get_by_id(task.id()).is_some())
+                .map(|(name, _)| name.clone());
+            (project.id(), column, task.id())
+        });
+
+        if let Some((running_project, Some(running_column), running_task)) = &running {
+            self.projects_mut()
+                .modify_item_with_id(*running_project, |project| {
+                    project
+                        .columns
+                        .get_mut(running_column)
+                        .modify_item_with_id(*running_task, Task::stop_timer)
+                });
+        }
+
+        let stopped = running.is_some_and(|(_, _, running_task)| running_task == task);
+        self.projects_mut()
+            .modify_item_with_id(project, |project| {
+                project
+                    .columns
+                    .get_mut(column)
+                    .modify_item_with_id(task, |task| {
+                        let action = match stopped {
+                            true => "stopped",
+                            false => {
+                                task.start_timer(column);
+                                "started"
+                            }
+                        };
+
+                        format!("{action} timer of '{}'", task.title)
+                    })
+            })
+            .flatten()
+    }
+
+    /// Time tracked since the given time, or in total, of the projects that have any
+    pub fn time_report(&self, since: Option<Timestamp>, config: &Config) -> Vec<ProjectTime<'_>> {
+        let column_position = |name: &str| {
+            config
+                .column_configs()
+                .position(|column| column.name == name)
+                .unwrap_or(usize::MAX)
+        };
+
+        let now = Local::now();
+        let projects = self.projects.iter().map(|project| {
+            let mut columns = Vec::<(&str, TimeDelta)>::new();
+            for (name, column) in project.columns.iter() {
+                for interval in column.iter().flat_map(|task| &task.work) {
+                    let name = interval.column.as_deref().unwrap_or(name);
+                    let time = interval.duration(since, now);
+                    match columns.iter_mut().find(|(other, _)| *other == name) {
+                        Some((_, total)) => *total += time,
+                        None => columns.push((name, time))
+                    }
+                }
+            }
+
+            columns.retain(|(_, time)| *time > TimeDelta::zero());
+            columns.sort_by_key(|(name, _)| column_position(name));
+            ProjectTime { project, columns }
+        });
+
+        Vec::from_iter(projects.filter(|project| !project.columns.is_empty()))
+    }
+}
@@ -0,0 +1,75 @@
+use kraban_config::{ColumnConfig, Config};
+
+use crate::{ProjectId, State, Task, TaskId};
+
+impl State {
+    /// Moves the task to a column of another project, it keeps everything but its number.
+    /// Returns the description of the move
+    pub fn move_task_to_project(
+        &mut self,
+        from: ProjectId,
+        column: &str,
+        task: TaskId,
+        to: ProjectId,
+        to_column: &ColumnConfig,
+        config: &Config
+    ) -> Option<String> {
+        self.project(to)?;
+        let task = self
+            .projects_mut()
+            .modify_item_with_id(from, |project| {
+                project.columns.get_mut(column).remove_by_id(task)
+            })
+            .flatten()?;
+
+        self.receive_task(task, to, to_column, config)
+            .map(|(task, project)| format!("moved '{task}' to {project}/{}", to_column.name))
+    }
+
+    /// Copies the task to a column of a project, which can be its own.
+    /// Returns the description of the copy
+    pub fn copy_task_to_project(
+        &mut self,
+        from: ProjectId,
+        column: &str,
+        task: TaskId,
+        to: ProjectId,
+        to_column: &ColumnConfig,
+        config: &Config
+    ) -> Option<String> {
+        let copy = self
+            .project(from)?
+            .columns
+            .get(column)
+            .get_by_id(task)?
+            .duplicate();
+
+        self.receive_task(copy, to, to_column, config)
+            .map(|(task, project)| format!("copied '{task}' to {project}/{}", to_column.name))
+    }
+
+    /// Adds a copy of the project next to it, returns the description of the change
+    pub fn duplicate_project(&mut self, project: ProjectId, config: &Config) -> Option<String> {
+        let idx = self.projects.position_by_id(project)?;
+        let project = &self.projects[idx];
+        let description = format!("duplicated project '{}'", project.title);
+        let copy = project.duplicate(config);
+        self.projects_mut().insert(idx + 1, copy);
+        Some(description)
+    }
+
+    /// Returns the titles of the task and the project
+    fn receive_task(
+        &mut self,
+        mut task: Task,
+        to: ProjectId,
+        column: &ColumnConfig,
+        config: &Config
+    ) -> Option<(String, String)> {
+        self.projects_mut().modify_item_with_id(to, |project| {
+            project.number_task(&mut task);
+            let task = project.receive_task(task, column, config);
+            (task.title.clone(), project.title.clone())
+        })
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::{Days, Local};
+use color_eyre::eyre::{OptionExt, Result, bail};
+use kraban_config::Config;
+use kraban_lib::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::{Identifiable, Project, ProjectId, State, Task, TaskId, TrashId};
+
+pub type Trash = Vec<TrashItem>;
+
+/// A deleted project or task, kept so that it can be restored to where it was
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TrashItem {
+    id: TrashId,
+    pub deleted_at: Timestamp,
+    pub content: Trashed
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Trashed {
+    Project(Project),
+    Task {
+        project: ProjectId,
+        /// Shown in the trash even when the project doesn't exist anymore
+        project_title: String,
+        column: String,
+        task: Task
+    }
+}
+
+impl TrashItem {
+    fn new(content: Trashed) -> Self {
+        Self {
+            id: TrashId::new(),
+            deleted_at: Local::now(),
+            content
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        match &self.content {
+            Trashed::Project(project) => &project.title,
+            Trashed::Task { task, .. } => &task.title
+        }
+    }
+}
+
+impl Identifiable for TrashItem {
+    type Id = TrashId;
+    fn id(&self) -> TrashId { self.id }
+}
+
+impl State {
+    /// Newest items first
+    pub fn trash(&self) -> &[TrashItem] { &self.trash }
+    fn trash_mut(&mut self) -> &mut Trash {
+        self.should_save = true;
+        self.history.snapshot_if_needed(&self.projects, &self.trash);
+        &mut self.trash
+    }
+
+    pub fn trash_project(&mut self, id: ProjectId) -> Option<&Project> {
+        let mut project = self.projects_mut().remove_by_id(id)?;
+        project.stop_timers();
+        self.trash_mut()
+            .insert(0, TrashItem::new(Trashed::Project(project)));
+        match &self.trash[0].content {
+            Trashed::Project(project) => Some(project),
+            Trashed::Task { .. } => None
+        }
+    }
+
+    pub fn trash_task(&mut self, project: ProjectId, column: &str, task: TaskId) -> Option<&Task> {
+        let (project_title, task) =
+            self.projects_mut()
+                .modify_item_with_id(project, |project| {
+                    let mut task = project.columns.get_mut(column).remove_by_id(task)?;
+                    task.stop_timer();
+                    Some((project.title.clone(), task))
+                })??;
+
+        let content = Trashed::Task {
+            project,
+            project_title,
+            column: column.to_string(),
+            task
+        };
+
+        self.trash_mut().insert(0, TrashItem::new(content));
+        match &self.trash[0].content {
+            Trashed::Task { task, .. } => Some(task),
+            Trashed::Project(_) => None
+        }
+    }
+
+    /// Puts the item back where it was deleted from and returns the description of the change
+    pub fn restore_from_trash(&mut self, id: TrashId) -> Result<String> {
+        let idx = self
+            .trash
+            .iter()
+            .position(|item| item.id == id)
+            .ok_or_eyre("The item is not in the trash")?;
+        if let Trashed::Task {
+            project,
+            project_title,
+            ..
+        } = &self.trash[idx].content
+            && self.project(*project).is_none()
+        {
+            bail!("Restore project '{project_title}' first")
+        }
+
+        let description = match self.trash_mut().remove(idx).content {
+            Trashed::Project(project) => {
+                let description = format!("restored project '{}'", project.title);
+                self.projects_mut().push(project);
+                description
+            }
+            Trashed::Task {
+                project,
+                column,
+                task,
+                ..
+            } => {
+                let description = format!("restored task '{}'", task.title);
+                let f = |project: &mut Project| project.columns.get_mut(&column).push(task);
+                self.projects_mut().modify_item_with_id(project, f);
+                description
+            }
+        };
+
+        Ok(description)
+    }
+
+    /// Deletes the item for good and returns its title
+    pub fn purge_from_trash(&mut self, id: TrashId) -> Option<String> {
+        let idx = self.trash.iter().position(|item| item.id == id)?;
+        let item = self.trash_mut().remove(idx);
+        Some(item.title().to_string())
+    }
+
+    /// Purges the items that have been in the trash for longer than configured. This can't be undone
+    pub(crate) fn purge_expired_trash(&mut self, config: &Config) {
+        if config.trash_days == 0 {
+            return;
+        }
+
+        let Some(limit) = Local::now().checked_sub_days(Days::new(config.trash_days.into())) else {
+            return
+        };
+
+        let len = self.trash.len();
+        self.trash.retain(|item| item.deleted_at > limit);
+        self.should_save |= self.trash.len() != len;
+    }
+}
@@ -0,0 +1,141 @@
+use kraban_config::Config;
+use kraban_state::{Identifiable, Project, ProjectId, State};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::Widget
+};
+
+use crate::{
+    ArchivePrompt,
+    keyhints::Keyhints,
+    projects::{PROJECT_CONSTRAINTS, project_row},
+    prompt::delete::ProjectDeleteConfirmation,
+    table::{Table, TableQuery, table}
+};
+
+pub enum Response {
+    OpenPrompt(ArchiveView, ArchivePrompt),
+    SwitchToProjectsView,
+    RestoreSelected(ArchiveView),
+    Update(ArchiveView)
+}
+
+/// Lists archived projects, which can be restored or deleted for good
+#[derive(Default)]
+pub struct ArchiveView(table!(ArchivedProjectsQuery));
+impl ArchiveView {
+    pub fn modify_selected_project<T>(
+        &self,
+        f: impl FnOnce(&mut Project) -> T,
+        state: &mut State,
+        config: &Config
+    ) -> Option<T> {
+        let id = self.0.selected_id(state, config)?;
+        state.projects_mut().modify_item_with_id(id, f)
+    }
+
+    pub fn delete_selected_project(&self, state: &mut State, config: &Config) -> Option<Project> {
+        let id = self.0.selected_id(state, config)?;
+        state.projects_mut().remove_by_id(id)
+    }
+
+    pub fn render(
+        &mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &State,
+        config: &Config,
+        focused: bool
+    ) {
+        Line::from("Archived projects".bold().fg(config.app_color))
+            .centered()
+            .render(area, buf);
+        let area = Rect {
+            y: area.y + 1,
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+
+        if self.0.len(state, config) == 0 {
+            Line::from("No archived projects".italic().dark_gray())
+                .centered()
+                .render(area, buf);
+        } else {
+            self.0.render(area, buf, state, config, focused)
+        }
+    }
+
+    pub fn on_key(mut self, key: KeyEvent, state: &State, config: &Config) -> Response {
+        const NONE: KeyModifiers = KeyModifiers::NONE;
+        let selected = self
+            .0
+            .selected_id(state, config)
+            .and_then(|id| state.project(id));
+        match (key.code, key.modifiers, selected) {
+            (KeyCode::Esc, NONE, _) => Response::SwitchToProjectsView,
+            (KeyCode::Enter, NONE, Some(_)) => Response::RestoreSelected(self),
+            (KeyCode::Delete | KeyCode::Backspace, NONE, Some(project)) => {
+                let prompt = ProjectDeleteConfirmation::new(project.id());
+                Response::OpenPrompt(self, prompt.into())
+            }
+            _ => {
+                self.0.on_key(key);
+                Response::Update(self)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ArchivedProjectsQuery;
+impl ArchivedProjectsQuery {
+    fn projects<'a>(&self, state: &'a State, config: &Config) -> impl Iterator<Item = &'a Project> {
+        state
+            .sorted_projects(config)
+            .filter(|project| project.archived)
+    }
+}
+
+impl TableQuery<3> for ArchivedProjectsQuery {
+    type Id = ProjectId;
+    fn ids<'a>(&self, state: &'a State, config: &'a Config) -> impl Iterator<Item = ProjectId> {
+        self.projects(state, config).map(Project::id)
+    }
+
+    fn rows<'a>(
+        &self,
+        state: &'a State,
+        config: &'a Config
+    ) -> impl Iterator<Item = [Line<'a>; 3]> {
+        self.projects(state, config)
+            .map(move |project| project_row(project, config))
+    }
+
+    fn len(&self, state: &State, config: &Config) -> usize { self.projects(state, config).count() }
+    const CONSTRAINTS: [Constraint; 3] = PROJECT_CONSTRAINTS;
+}
+
+impl Keyhints for ArchiveView {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        const ITEM_REQUIRING_KEYHINTS: [(&str, &str); 2] = [
+            ("Enter", "Restore"),
+            ("Delete/Backspace", "Delete permanently")
+        ];
+
+        let item_requiring_iter = self
+            .0
+            .keyhints(state, config)
+            .into_iter()
+            .chain(ITEM_REQUIRING_KEYHINTS);
+
+        (self.0.len(state, config) != 0)
+            .then_some(item_requiring_iter)
+            .into_iter()
+            .flatten()
+            .chain([("Esc", "Back to main view")])
+    }
+}
@@ -1,28 +1,33 @@
-use std::iter;
-
 use kraban_config::Config;
 use kraban_lib::now;
-use kraban_state::{DueTask, State};
+use kraban_state::{DueTask, Identifiable, State, TaskId};
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
     layout::{Constraint, Rect},
     style::Stylize,
-    text::Line
+    text::Line,
+    widgets::Widget
 };
 use time::Date;
 
 use crate::{
+    DueTasksPrompt,
     keyhints::Keyhints,
+    prompt::{
+        move_to_project::MoveToProjectPrompt,
+        tags::{TagsAction, TagsPrompt}
+    },
     table::{Table, TableQuery, table},
     task::TasksView,
     utils::{
         DIFFICULTY_CONSTRAINT, DUE_DATE_CONSTRAINT, PRIORITY_CONSTRAINT, difficulty_to_line,
-        due_date_to_line, priority_to_color, priority_to_line
+        due_date_to_line, priority_to_color, priority_to_line, title_to_line
     }
 };
 
 pub enum Response<'a> {
+    OpenPrompt(DueTasksView, DueTasksPrompt),
     SwitchToTasksView(TasksView<'a>),
     SwitchToProjectsView(DueTasksView),
     Update(DueTasksView)
@@ -39,19 +44,54 @@ impl DueTasksView {
         config: &Config,
         focused: bool
     ) {
+        let area = match self.0.tag_filter.as_slice() {
+            [] => area,
+            tags => {
+                let filter_msg = format!("Filtered by tags: {}", tags.join(", "));
+                Line::from(filter_msg.italic().dark_gray())
+                    .centered()
+                    .render(area, buf);
+                Rect {
+                    y: area.y + 1,
+                    height: area.height.saturating_sub(1),
+                    ..area
+                }
+            }
+        };
+
         self.0.render(area, buf, state, config, focused);
     }
 
+    pub fn set_tag_filter(&mut self, tags: Vec<String>) { self.0.tag_filter = tags }
+    pub fn selected_task<'a>(&self, state: &'a State, config: &'a Config) -> Option<DueTask<'a>> {
+        let id = self.0.selected_id(state, config)?;
+        self.0
+            .due_tasks(state, config)
+            .find(|due_task| due_task.task.id() == id)
+    }
+
     pub fn on_key<'a>(mut self, key: KeyEvent, state: &State, config: &'a Config) -> Response<'a> {
         const NONE: KeyModifiers = KeyModifiers::NONE;
-        match (key.code, key.modifiers, self.0.selected(state, config)) {
+        let selected = self.0.selected_id(state, config).and_then(|id| {
+            self.0
+                .due_tasks(state, config)
+                .find(|due_task| due_task.task.id() == id)
+        });
+        match (key.code, key.modifiers, selected) {
             (KeyCode::Tab, NONE, _) => Response::SwitchToProjectsView(self),
-            (KeyCode::Enter, NONE, Some(idx)) => {
-                let selected = state.due_tasks(config).nth(idx).unwrap();
+            (KeyCode::Char('f'), NONE, _) => {
+                let tags = self.0.tag_filter.clone();
+                Response::OpenPrompt(self, TagsPrompt::new(TagsAction::Filter, tags).into())
+            }
+            (KeyCode::Char('m'), NONE, Some(selected)) => {
+                let prompt = MoveToProjectPrompt::new(selected.project.id());
+                Response::OpenPrompt(self, prompt.into())
+            }
+            (KeyCode::Enter, NONE, Some(selected)) => {
                 Response::SwitchToTasksView(TasksView::with_specific_task(
-                    selected.project_idx,
+                    selected.project.id(),
                     &selected.column_config.name,
-                    selected.idx,
+                    selected.task.id(),
                     config
                 ))
             }
@@ -64,9 +104,31 @@ impl DueTasksView {
 }
 
 #[derive(Default)]
-struct DueTaskQuery;
+struct DueTaskQuery {
+    /// Only tasks with all of these tags are shown
+    tag_filter: Vec<String>
+}
+
+impl DueTaskQuery {
+    fn due_tasks<'a>(
+        &self,
+        state: &'a State,
+        config: &'a Config
+    ) -> impl Iterator<Item = DueTask<'a>> {
+        state
+            .due_tasks(config)
+            .filter(|task| task.task.has_tags(&self.tag_filter))
+    }
+}
+
 impl TableQuery<6> for DueTaskQuery {
-    fn len(&self, state: &State, config: &Config) -> usize { state.due_tasks(config).count() }
+    type Id = TaskId;
+    fn ids<'a>(&self, state: &'a State, config: &'a Config) -> impl Iterator<Item = TaskId> {
+        self.due_tasks(state, config)
+            .map(|due_task| due_task.task.id())
+    }
+
+    fn len(&self, state: &State, config: &Config) -> usize { self.due_tasks(state, config).count() }
 
     const CONSTRAINTS: [Constraint; 6] = [
         DUE_DATE_CONSTRAINT,
@@ -83,42 +145,56 @@ impl TableQuery<6> for DueTaskQuery {
         config: &'a Config
     ) -> impl Iterator<Item = [Line<'a>; 6]> {
         let now = now();
-        state
-            .due_tasks(config)
-            .map(move |task| due_task_rows(now, task))
+        self.due_tasks(state, config)
+            .map(move |task| due_task_rows(now, task, state, config))
     }
 }
 
-fn due_task_rows(now: Date, task: DueTask) -> [Line; 6] {
+/// Blocked tasks are dimmed until their blockers are done
+fn due_task_rows<'a>(
+    now: Date,
+    task: DueTask<'a>,
+    state: &State,
+    config: &Config
+) -> [Line<'a>; 6] {
     let project_title_color = task
         .project
         .priority
-        .map(priority_to_color)
+        .as_ref()
+        .map(|priority| priority_to_color(priority, config))
         .unwrap_or_default();
+    let blocked = state.is_blocked(task.task);
     [
         due_date_to_line(task.due_date, now),
         Line::from(task.project.title.as_str()).fg(project_title_color),
         Line::from(task.column_config.name.as_str())
             .fg(task.column_config.color)
             .italic(),
-        task.priority.map(priority_to_line).unwrap_or_default(),
-        task.difficulty.map(difficulty_to_line).unwrap_or_default(),
-        Line::from(task.title)
+        task.priority
+            .map(|priority| priority_to_line(priority, config))
+            .unwrap_or_default(),
+        task.difficulty
+            .map(|difficulty| difficulty_to_line(difficulty, config))
+            .unwrap_or_default(),
+        title_to_line(task.task, state, config)
     ]
+    .map(|line| match blocked {
+        true => line.dim(),
+        false => line
+    })
 }
 
 impl Keyhints for DueTasksView {
     fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
-        let item_requiring_iter = self
-            .0
-            .keyhints(state, config)
-            .into_iter()
-            .chain(iter::once(("Enter", "Switch to task")));
+        let item_requiring_iter = self.0.keyhints(state, config).into_iter().chain([
+            ("Enter", "Switch to task"),
+            ("m", "Move/copy task to project")
+        ]);
 
         (self.0.len(state, config) != 0)
             .then_some(item_requiring_iter)
             .into_iter()
             .flatten()
-            .chain(iter::once(("Tab", "Switch to project view")))
+            .chain([("f", "Filter by tags"), ("Tab", "Switch to project view")])
     }
 }
@@ -0,0 +1,144 @@
+use kraban_config::Config;
+use kraban_lib::format_elapsed;
+use kraban_state::{Durations, FlowTimes, State};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget}
+};
+
+use crate::keyhints::Keyhints;
+
+const THROUGHPUT_WEEKS: u32 = 8;
+const NAME_WIDTH: usize = 20;
+
+pub enum Response {
+    SwitchToProjectsView,
+    Update(FlowView)
+}
+
+/// Lead and cycle times of the completed tasks and the weekly throughput, see [`State::flow_report`]
+#[derive(Default)]
+pub struct FlowView {
+    scroll: u16
+}
+
+impl FlowView {
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
+        Line::from("Flow report".bold().fg(config.app_color))
+            .centered()
+            .render(area, buf);
+        let area = Rect {
+            y: area.y + 1,
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+
+        let report = state.flow_report(THROUGHPUT_WEEKS, config);
+        if report.projects.is_empty() {
+            Line::from("No tasks were completed yet".italic().dark_gray())
+                .centered()
+                .render(area, buf);
+            return;
+        }
+
+        let mut lines = vec![header("Project", config)];
+        lines.extend(
+            report
+                .projects
+                .iter()
+                .map(|(project, times)| row(project.title.as_str().into(), times))
+        );
+        lines.push(Line::default());
+        lines.push(header("Difficulty", config));
+        lines.extend(report.difficulties.iter().map(|(difficulty, times)| {
+            let name = match difficulty {
+                Some(name) => {
+                    let color = config
+                        .difficulty_level(name)
+                        .map_or(Color::DarkGray, |level| level.color);
+                    name.fg(color)
+                }
+                None => "None".dark_gray()
+            };
+            row(name, times)
+        }));
+        lines.push(Line::default());
+        lines.push(Line::from("Weekly throughput".bold().fg(config.app_color)));
+        lines.extend(report.throughput.iter().map(|(week, done)| {
+            Line::from_iter([
+                format!("{week} ").into(),
+                "█".repeat(*done).fg(config.app_color),
+                format!(" {done}").into()
+            ])
+        }));
+
+        self.scroll = self.scroll.min(lines.len().saturating_sub(1) as u16);
+        Paragraph::new(lines)
+            .scroll((self.scroll, 0))
+            .render(area, buf);
+    }
+
+    pub fn on_key(mut self, key: KeyEvent) -> Response {
+        const NONE: KeyModifiers = KeyModifiers::NONE;
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, NONE) => return Response::SwitchToProjectsView,
+            (KeyCode::Up, NONE) => self.scroll = self.scroll.saturating_sub(1),
+            (KeyCode::Down, NONE) => self.scroll = self.scroll.saturating_add(1),
+            (KeyCode::Home, NONE) => self.scroll = 0,
+            _ => {}
+        }
+
+        Response::Update(self)
+    }
+}
+
+fn header<'a>(title: &'a str, config: &Config) -> Line<'a> {
+    Line::from_iter([
+        format!("{title:NAME_WIDTH$}").bold().fg(config.app_color),
+        format!(
+            " {:>5}  {:^35}  {:^35}",
+            "done", "lead time (avg/p50/p85/p95)", "cycle time (avg/p50/p85/p95)"
+        )
+        .bold()
+    ])
+}
+
+fn row<'a>(name: Span<'a>, times: &FlowTimes) -> Line<'a> {
+    let padding = NAME_WIDTH.saturating_sub(name.width());
+    let count = times.lead.as_ref().map_or(0, |lead| lead.count);
+    Line::from_iter([
+        name,
+        format!(
+            "{:padding$} {count:>5}  {}  {}",
+            "",
+            durations(times.lead.as_ref()),
+            durations(times.cycle.as_ref())
+        )
+        .into()
+    ])
+}
+
+fn durations(durations: Option<&Durations>) -> String {
+    let Some(durations) = durations else {
+        return format!("{:^35}", "-");
+    };
+
+    [
+        durations.average,
+        durations.p50,
+        durations.p85,
+        durations.p95
+    ]
+    .map(|duration| format!("{:>8}", format_elapsed(duration)))
+    .join(" ")
+}
+
+impl Keyhints for FlowView {
+    fn keyhints(&self, _: &State, _: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        [("Up/Down", "Scroll"), ("Esc", "Back to main view")]
+    }
+}
@@ -5,7 +5,10 @@ use ratatui::{
     text::{Line, Span, Text}
 };
 
-use crate::{ProjectsPrompt, TasksPrompt, Ui, UiState, main_view::MainViewFocus};
+use crate::{
+    ArchivePrompt, DueTasksPrompt, ProjectsPrompt, TasksPrompt, Ui, UiState,
+    main_view::MainViewFocus
+};
 
 fn keyhints_to_text<'a>(
     hints: impl Iterator<Item = (&'a str, &'a str)>,
@@ -77,11 +80,27 @@ impl Ui<'_> {
             UiState::ProjectsPrompt(_, _, ProjectsPrompt::PriorityPrompt(hints)),
             UiState::TasksPrompt(_, TasksPrompt::PriorityPrompt(hints)),
             UiState::ProjectsPrompt(_, _, ProjectsPrompt::ProjectDeleteConfirmation(hints)),
+            UiState::ProjectsPrompt(_, _, ProjectsPrompt::BoardsPrompt(hints)),
+            UiState::ProjectsPrompt(_, _, ProjectsPrompt::OrphanedColumnsPrompt(hints)),
+            UiState::ProjectsPrompt(_, _, ProjectsPrompt::TemplatesPrompt(hints)),
             UiState::TasksView(hints),
+            UiState::ArchiveView(hints),
+            UiState::TrashView(hints),
+            UiState::FlowView(hints),
+            UiState::ArchivePrompt(_, ArchivePrompt::ProjectDeleteConfirmation(hints)),
             UiState::TasksPrompt(_, TasksPrompt::DifficultyPrompt(hints)),
             UiState::TasksPrompt(_, TasksPrompt::DueDatePrompt(hints)),
             UiState::TasksPrompt(_, TasksPrompt::MoveToColumnPrompt(hints)),
+            UiState::TasksPrompt(_, TasksPrompt::MoveToProjectPrompt(hints)),
             UiState::TasksPrompt(_, TasksPrompt::TaskDeleteConfirmation(hints)),
+            UiState::TasksPrompt(_, TasksPrompt::NotesPrompt(hints)),
+            UiState::TasksPrompt(_, TasksPrompt::ChecklistPrompt(hints)),
+            UiState::TasksPrompt(_, TasksPrompt::TagsPrompt(hints)),
+            UiState::TasksPrompt(_, TasksPrompt::BlockersPrompt(hints)),
+            UiState::TasksPrompt(_, TasksPrompt::RecurrencePrompt(hints)),
+            UiState::TasksPrompt(_, TasksPrompt::TaskDetailPrompt(hints)),
+            UiState::DueTasksPrompt(_, _, DueTasksPrompt::TagsPrompt(hints)),
+            UiState::DueTasksPrompt(_, _, DueTasksPrompt::MoveToProjectPrompt(hints)),
         )
     }
 }
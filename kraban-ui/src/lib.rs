@@ -1,4 +1,6 @@
+mod archive;
 mod due_tasks;
+mod flow;
 mod keyhints;
 mod list;
 mod main_view;
@@ -7,60 +9,122 @@ mod prompt;
 mod render;
 mod table;
 mod task;
+mod trash;
 mod utils;
 
-use kraban_config::{AlwaysOpen, Config};
-use kraban_state::{Project, SetPriority, State, Task};
+use std::mem;
+
+use flow::FlowView;
+use kraban_config::{AlwaysOpen, Board, ColumnConfig, Config, WipLimitPolicy};
+use kraban_state::{Identifiable, Project, ProjectId, SetPriority, State, Task, TaskId};
 use main_view::MainViewFocus;
 use projects::ProjectsView;
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use task::TasksView;
+use trash::TrashView;
 
 use crate::{
+    archive::ArchiveView,
     due_tasks::DueTasksView,
     prompt::{
-        ProjectsPrompt, TasksPrompt, delete,
+        ArchivePrompt, DueTasksPrompt, ProjectsPrompt, TasksPrompt, blockers,
+        boards::{self, BoardsPrompt},
+        checklist, delete,
         difficulty::{self, DifficultyPrompt},
         due_date::{self, DueDatePrompt},
-        input, move_to_column,
-        priority::{self, PriorityPrompt}
+        input::{self, InputAction, InputPrompt},
+        move_to_column,
+        move_to_project::{self, Transfer},
+        notes,
+        orphaned_columns::{self, OrphanedColumnsPrompt},
+        priority::{self, PriorityPrompt},
+        recurrence,
+        tags::{self, TagsAction},
+        templates
     }
 };
 
-pub struct Ui<'a>(UiState<'a>);
+/// The second field is a message shown in the bottom of the app until the next key press
+pub struct Ui<'a>(UiState<'a>, Option<String>);
 enum UiState<'a> {
     MainView(ProjectsView, DueTasksView, MainViewFocus),
     ProjectsPrompt(ProjectsView, DueTasksView, ProjectsPrompt),
+    DueTasksPrompt(ProjectsView, DueTasksView, DueTasksPrompt),
     TasksView(TasksView<'a>),
-    TasksPrompt(TasksView<'a>, TasksPrompt<'a>)
+    TasksPrompt(TasksView<'a>, TasksPrompt<'a>),
+    ArchiveView(ArchiveView),
+    ArchivePrompt(ArchiveView, ArchivePrompt),
+    TrashView(TrashView),
+    FlowView(FlowView)
 }
 
 impl<'a> From<UiState<'a>> for Response<'a> {
-    fn from(value: UiState<'a>) -> Self { Self::Update(Ui(value)) }
+    fn from(value: UiState<'a>) -> Self { Self::Update(Ui(value, None)) }
 }
 
 impl Default for Ui<'_> {
     fn default() -> Self {
-        Self(UiState::MainView(
-            ProjectsView::default(),
-            DueTasksView::default(),
-            MainViewFocus::Projects
-        ))
+        Self(
+            UiState::MainView(
+                ProjectsView::default(),
+                DueTasksView::default(),
+                MainViewFocus::Projects
+            ),
+            None
+        )
     }
 }
 
 #[allow(clippy::large_enum_variant)]
 pub enum Response<'a> {
     Quit,
-    Update(Ui<'a>)
+    Update(Ui<'a>),
+    /// The app has to be restarted with the config of the board
+    SwitchBoard(Board)
 }
 
 impl<'a> Ui<'a> {
+    /// Asks about the columns missing from the config first, if there are any
+    pub fn new(state: &State, config: &Config) -> Self {
+        Self::with_orphaned_columns_prompt(
+            ProjectsView::default(),
+            DueTasksView::default(),
+            state,
+            config
+        )
+    }
+
+    fn with_orphaned_columns_prompt(
+        projects: ProjectsView,
+        due_tasks: DueTasksView,
+        state: &State,
+        config: &Config
+    ) -> Self {
+        let view = match OrphanedColumnsPrompt::new(state, config) {
+            Some(prompt) => UiState::ProjectsPrompt(projects, due_tasks, prompt.into()),
+            None => UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
+        };
+
+        Self(view, None)
+    }
+
     pub fn on_key(self, key: KeyEvent, state: &mut State, config: &'a Config) -> Response<'a> {
         match self.0 {
             _ if key.code == KeyCode::Char('q') && key.modifiers == KeyModifiers::CONTROL => {
                 return Response::Quit
             }
+            view @ (UiState::MainView(..)
+            | UiState::TasksView(..)
+            | UiState::ArchiveView(..)
+            | UiState::TrashView(..))
+                if matches!(
+                    (key.code, key.modifiers),
+                    (KeyCode::Char('u'), KeyModifiers::NONE)
+                        | (KeyCode::Char('r'), KeyModifiers::CONTROL)
+                ) =>
+            {
+                return Self::undo_or_redo(view, key, state)
+            }
             UiState::MainView(projects, due_tasks, MainViewFocus::Projects) => {
                 match projects.on_key(key, state, config) {
                     projects::Response::OpenPrompt(projects, projects_prompt) => {
@@ -73,10 +137,49 @@ impl<'a> Ui<'a> {
                     projects::Response::SwitchToDueTasksView(projects) => {
                         UiState::MainView(projects, due_tasks, MainViewFocus::DueTasks)
                     }
+                    projects::Response::SwitchToArchiveView => {
+                        UiState::ArchiveView(ArchiveView::default())
+                    }
+                    projects::Response::SwitchToTrashView => {
+                        UiState::TrashView(TrashView::default())
+                    }
+                    projects::Response::SwitchToFlowView => UiState::FlowView(FlowView::default()),
+                    projects::Response::OpenBoardSwitcher(projects) => match Config::boards() {
+                        Ok(boards) => UiState::ProjectsPrompt(
+                            projects,
+                            due_tasks,
+                            BoardsPrompt::new(boards, config).into()
+                        ),
+                        Err(error) => {
+                            return Response::Update(Ui(
+                                UiState::MainView(projects, due_tasks, MainViewFocus::Projects),
+                                Some(error.to_string())
+                            ))
+                        }
+                    },
+                    projects::Response::ArchiveSelected(projects) => {
+                        let f = |project: &mut Project| {
+                            project.archive();
+                            format!("archived project '{}'", project.title)
+                        };
+
+                        let description = projects.modify_selected_project(f, state, config);
+                        describe(state, description);
+                        UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
+                    }
+                    projects::Response::DuplicateSelected(projects) => {
+                        let id = projects.selected_id(state, config);
+                        let description = id.and_then(|id| state.duplicate_project(id, config));
+                        describe(state, description);
+                        UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
+                    }
                 }
             }
             UiState::MainView(projects, due_tasks, MainViewFocus::DueTasks) => {
                 match due_tasks.on_key(key, state, config) {
+                    due_tasks::Response::OpenPrompt(due_tasks, due_tasks_prompt) => {
+                        UiState::DueTasksPrompt(projects, due_tasks, due_tasks_prompt)
+                    }
                     due_tasks::Response::SwitchToTasksView(tasks_view) => {
                         UiState::TasksView(tasks_view)
                     }
@@ -93,11 +196,92 @@ impl<'a> Ui<'a> {
             {
                 UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
             }
+            UiState::DueTasksPrompt(projects, due_tasks, ..)
+                if key.code == KeyCode::Esc && key.modifiers == KeyModifiers::NONE =>
+            {
+                UiState::MainView(projects, due_tasks, MainViewFocus::DueTasks)
+            }
             UiState::TasksPrompt(tasks_view, ..)
                 if key.code == KeyCode::Esc && key.modifiers == KeyModifiers::NONE =>
             {
                 UiState::TasksView(tasks_view)
             }
+            UiState::ArchivePrompt(archive, ..)
+                if key.code == KeyCode::Esc && key.modifiers == KeyModifiers::NONE =>
+            {
+                UiState::ArchiveView(archive)
+            }
+            UiState::ArchiveView(archive) => match archive.on_key(key, state, config) {
+                archive::Response::OpenPrompt(archive, archive_prompt) => {
+                    UiState::ArchivePrompt(archive, archive_prompt)
+                }
+                archive::Response::SwitchToProjectsView => UiState::MainView(
+                    ProjectsView::default(),
+                    DueTasksView::default(),
+                    MainViewFocus::Projects
+                ),
+                archive::Response::RestoreSelected(archive) => {
+                    let f = |project: &mut Project| {
+                        project.archived = false;
+                        format!("restored project '{}'", project.title)
+                    };
+
+                    let description = archive.modify_selected_project(f, state, config);
+                    describe(state, description);
+                    UiState::ArchiveView(archive)
+                }
+                archive::Response::Update(archive) => UiState::ArchiveView(archive)
+            },
+            UiState::TrashView(trash) => match trash.on_key(key, state, config) {
+                trash::Response::SwitchToProjectsView => UiState::MainView(
+                    ProjectsView::default(),
+                    DueTasksView::default(),
+                    MainViewFocus::Projects
+                ),
+                trash::Response::RestoreSelected(trash) => {
+                    let id = trash.selected_id(state, config);
+                    match id.map(|id| state.restore_from_trash(id)) {
+                        Some(Err(error)) => {
+                            return Response::Update(Ui(
+                                UiState::TrashView(trash),
+                                Some(error.to_string())
+                            ))
+                        }
+                        description => describe(state, description.and_then(Result::ok))
+                    }
+
+                    UiState::TrashView(trash)
+                }
+                trash::Response::PurgeSelected(trash) => {
+                    let id = trash.selected_id(state, config);
+                    let title = id.and_then(|id| state.purge_from_trash(id));
+                    describe(state, title.map(|title| format!("purged '{title}'")));
+                    UiState::TrashView(trash)
+                }
+                trash::Response::Update(trash) => UiState::TrashView(trash)
+            },
+            UiState::FlowView(flow) => match flow.on_key(key) {
+                flow::Response::SwitchToProjectsView => UiState::MainView(
+                    ProjectsView::default(),
+                    DueTasksView::default(),
+                    MainViewFocus::Projects
+                ),
+                flow::Response::Update(flow) => UiState::FlowView(flow)
+            },
+            UiState::ArchivePrompt(archive, ArchivePrompt::ProjectDeleteConfirmation(prompt)) => {
+                match prompt.on_key(key) {
+                    delete::Response::Delete => {
+                        let project = archive.delete_selected_project(state, config);
+                        let description =
+                            project.map(|project| format!("deleted project '{}'", project.title));
+                        describe(state, description);
+                        UiState::ArchiveView(archive)
+                    }
+                    delete::Response::Update(prompt) => {
+                        UiState::ArchivePrompt(archive, prompt.into())
+                    }
+                }
+            }
 
             UiState::ProjectsPrompt(projects, due_tasks, ProjectsPrompt::InputPrompt(prompt)) => {
                 match prompt.on_key(key) {
@@ -105,11 +289,13 @@ impl<'a> Ui<'a> {
                         UiState::ProjectsPrompt(projects, due_tasks, prompt.into())
                     }
                     input::Response::Rename(title) => {
-                        projects.modify_selected_project(
-                            |project| project.title = title,
-                            state,
-                            config
-                        );
+                        let f = |project: &mut Project| {
+                            let old_title = mem::replace(&mut project.title, title);
+                            format!("renamed '{old_title}' to '{}'", project.title)
+                        };
+
+                        let description = projects.modify_selected_project(f, state, config);
+                        describe(state, description);
 
                         UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
                     }
@@ -121,12 +307,44 @@ impl<'a> Ui<'a> {
                                 PriorityPrompt::new(Some(Project::new(title))).into()
                             )
                         } else {
+                            state.describe_change(format!("created project '{title}'"));
                             state.projects_mut().push(Project::new(title));
                             UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
                         }
                     }
+                    input::Response::SaveTemplate(name) => {
+                        let id = projects.selected_id(state, config);
+                        let message = id.and_then(|id| state.save_template(id, name, config));
+                        let view = UiState::MainView(projects, due_tasks, MainViewFocus::Projects);
+                        return Response::Update(Ui(view, message));
+                    }
+                    input::Response::NewFromTemplate { template, title } => {
+                        let description =
+                            state.create_project_from_template(&template, title, config);
+                        describe(state, description);
+                        UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
+                    }
                 }
             }
+            UiState::ProjectsPrompt(
+                projects,
+                due_tasks,
+                ProjectsPrompt::TemplatesPrompt(prompt)
+            ) => match prompt.on_key(key, state) {
+                templates::Response::Update(prompt) => {
+                    UiState::ProjectsPrompt(projects, due_tasks, prompt.into())
+                }
+                templates::Response::Pick(template) => {
+                    let action = InputAction::NewFromTemplate(template.clone());
+                    let prompt = InputPrompt::new(config, action, template);
+                    UiState::ProjectsPrompt(projects, due_tasks, prompt.into())
+                }
+                templates::Response::Delete(prompt, template) => {
+                    let message = state.delete_template(&template);
+                    let view = UiState::ProjectsPrompt(projects, due_tasks, prompt.into());
+                    return Response::Update(Ui(view, message));
+                }
+            },
             UiState::ProjectsPrompt(
                 projects,
                 due_tasks,
@@ -136,15 +354,18 @@ impl<'a> Ui<'a> {
                     UiState::ProjectsPrompt(projects, due_tasks, prompt.into())
                 }
                 priority::Response::ModifyCurrentlyCreatedItem(project) => {
+                    state.describe_change(format!("created project '{}'", project.title));
                     state.projects_mut().push(project);
                     UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
                 }
                 priority::Response::SetPriority(priority) => {
-                    projects.modify_selected_project(
-                        |project| project.priority = priority,
-                        state,
-                        config
-                    );
+                    let f = |project: &mut Project| {
+                        project.priority = priority;
+                        format!("changed priority of '{}'", project.title)
+                    };
+
+                    let description = projects.modify_selected_project(f, state, config);
+                    describe(state, description);
 
                     UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
                 }
@@ -155,13 +376,85 @@ impl<'a> Ui<'a> {
                 ProjectsPrompt::ProjectDeleteConfirmation(prompt)
             ) => match prompt.on_key(key) {
                 delete::Response::Delete => {
-                    projects.delete_selected_project(state, config);
+                    let project = projects.delete_selected_project(state, config);
+                    let description =
+                        project.map(|project| format!("deleted project '{}'", project.title));
+                    describe(state, description);
                     UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
                 }
                 delete::Response::Update(prompt) => {
                     UiState::ProjectsPrompt(projects, due_tasks, prompt.into())
                 }
             },
+            UiState::ProjectsPrompt(projects, due_tasks, ProjectsPrompt::BoardsPrompt(prompt)) => {
+                match prompt.on_key(key) {
+                    boards::Response::Update(prompt) => {
+                        UiState::ProjectsPrompt(projects, due_tasks, prompt.into())
+                    }
+                    boards::Response::Switch(board) if board != config.board => {
+                        return Response::SwitchBoard(board)
+                    }
+                    boards::Response::Switch(_) => {
+                        UiState::MainView(projects, due_tasks, MainViewFocus::Projects)
+                    }
+                }
+            }
+            UiState::ProjectsPrompt(
+                projects,
+                due_tasks,
+                ProjectsPrompt::OrphanedColumnsPrompt(prompt)
+            ) => {
+                let description = match prompt.on_key(key, config) {
+                    orphaned_columns::Response::Update(prompt) => {
+                        return UiState::ProjectsPrompt(projects, due_tasks, prompt.into()).into()
+                    }
+                    orphaned_columns::Response::MoveTo(column, to) => {
+                        state.remap_column(&column, to, config)
+                    }
+                    orphaned_columns::Response::Hide(column) => state.hide_column(&column),
+                    orphaned_columns::Response::Delete(column) => state.trash_column(&column)
+                };
+
+                describe(state, Some(description));
+                Self::with_orphaned_columns_prompt(projects, due_tasks, state, config).0
+            }
+            UiState::DueTasksPrompt(
+                projects,
+                mut due_tasks,
+                DueTasksPrompt::TagsPrompt(prompt)
+            ) => match prompt.on_key(key, config) {
+                tags::Response::Update(prompt) => {
+                    UiState::DueTasksPrompt(projects, due_tasks, prompt.into())
+                }
+                tags::Response::Submit(_, tags) => {
+                    due_tasks.set_tag_filter(tags);
+                    UiState::MainView(projects, due_tasks, MainViewFocus::DueTasks)
+                }
+            },
+            UiState::DueTasksPrompt(
+                projects,
+                due_tasks,
+                DueTasksPrompt::MoveToProjectPrompt(prompt)
+            ) => match prompt.on_key(key, state, config) {
+                move_to_project::Response::Update(prompt) => {
+                    UiState::DueTasksPrompt(projects, due_tasks, prompt.into())
+                }
+                move_to_project::Response::Transfer(transfer) => {
+                    let selected = due_tasks.selected_task(state, config).map(|task| {
+                        (
+                            task.project.id(),
+                            task.column_config.name.clone(),
+                            task.task.id()
+                        )
+                    });
+                    let message = selected.and_then(|(project, column, task)| {
+                        transfer_task(project, &column, task, transfer, state, config)
+                    });
+
+                    let view = UiState::MainView(projects, due_tasks, MainViewFocus::DueTasks);
+                    return Response::Update(Ui(view, message));
+                }
+            },
             UiState::TasksView(tasks_view) => match tasks_view.on_key(key, state, config) {
                 task::Response::OpenPrompt(tasks_view, tasks_prompt) => {
                     UiState::TasksPrompt(tasks_view, tasks_prompt)
@@ -171,6 +464,32 @@ impl<'a> Ui<'a> {
                     DueTasksView::default(),
                     MainViewFocus::Projects
                 ),
+                task::Response::DuplicateSelectedTask(tasks_view) => {
+                    let project = tasks_view.project();
+                    let column = tasks_view.focused_column();
+                    let transfer = Transfer {
+                        project,
+                        column,
+                        copy: true
+                    };
+                    let message = tasks_view
+                        .selected_task(state, config)
+                        .and_then(|(_, task)| {
+                            transfer_task(project, &column.name, task, transfer, state, config)
+                        });
+
+                    return Response::Update(Ui(UiState::TasksView(tasks_view), message));
+                }
+                task::Response::ToggleTimer(tasks_view) => {
+                    let description = tasks_view.toggle_selected_task_timer(state, config);
+                    describe(state, description);
+                    UiState::TasksView(tasks_view)
+                }
+                task::Response::ShiftSelectedTask(tasks_view, direction) => {
+                    let description = tasks_view.shift_selected_task(state, config, direction);
+                    describe(state, description);
+                    UiState::TasksView(tasks_view)
+                }
                 task::Response::Update(tasks_view) => UiState::TasksView(tasks_view)
             },
             UiState::TasksPrompt(tasks_view, TasksPrompt::InputPrompt(prompt)) => {
@@ -194,12 +513,20 @@ impl<'a> Ui<'a> {
                             DueDatePrompt::new(Some(Task::new(title)), None).into()
                         ),
                         _ => {
-                            tasks_view.push_task(Task::new(title), state);
-                            UiState::TasksView(tasks_view)
+                            return push_task(tasks_view, Task::new(title), state, config);
                         }
                     },
                     input::Response::Rename(title) => {
-                        tasks_view.modify_selected_task(state, config, |task| task.title = title);
+                        let f = |task: &mut Task| {
+                            let old_title = mem::replace(&mut task.title, title);
+                            format!("renamed '{old_title}' to '{}'", task.title)
+                        };
+
+                        let description = tasks_view.modify_selected_task(state, config, f);
+                        describe(state, description);
+                        UiState::TasksView(tasks_view)
+                    }
+                    input::Response::SaveTemplate(_) | input::Response::NewFromTemplate { .. } => {
                         UiState::TasksView(tasks_view)
                     }
                 }
@@ -225,76 +552,202 @@ impl<'a> Ui<'a> {
                                 )
                             }
                             _ => {
-                                tasks_view.push_task(task, state);
-                                UiState::TasksView(tasks_view)
+                                return push_task(tasks_view, task, state, config);
                             }
                         }
                     }
                     priority::Response::SetPriority(priority) => {
-                        let f = |task: &mut Task| task.set_priority(priority, config);
-                        tasks_view.modify_selected_task(state, config, f);
+                        let f = |task: &mut Task| {
+                            task.set_priority(priority, config);
+                            format!("changed priority of '{}'", task.title)
+                        };
+
+                        let description = tasks_view.modify_selected_task(state, config, f);
+                        describe(state, description);
                         UiState::TasksView(tasks_view)
                     }
                 }
             }
-            UiState::TasksPrompt(tasks_view, TasksPrompt::DifficultyPrompt(prompt)) => match prompt
-                .on_key(key)
-            {
-                difficulty::Response::Update(prompt) => {
-                    UiState::TasksPrompt(tasks_view, prompt.into())
-                }
-                difficulty::Response::ModifyCurrentlyCreatedTask(task) => {
-                    if config.always_open.due_date {
-                        let old_date = task.due_date();
-                        let due_date_prompt = DueDatePrompt::new(Some(task), old_date);
-                        UiState::TasksPrompt(tasks_view, due_date_prompt.into())
-                    } else {
-                        tasks_view.push_task(task, state);
+            UiState::TasksPrompt(tasks_view, TasksPrompt::DifficultyPrompt(prompt)) => {
+                match prompt.on_key(key, config) {
+                    difficulty::Response::Update(prompt) => {
+                        UiState::TasksPrompt(tasks_view, prompt.into())
+                    }
+                    difficulty::Response::ModifyCurrentlyCreatedTask(task) => {
+                        if config.always_open.due_date {
+                            let old_date = task.due_date();
+                            let due_date_prompt = DueDatePrompt::new(Some(task), old_date);
+                            UiState::TasksPrompt(tasks_view, due_date_prompt.into())
+                        } else {
+                            return push_task(tasks_view, task, state, config);
+                        }
+                    }
+                    difficulty::Response::SetDifficulty(difficulty) => {
+                        let f = |task: &mut Task| {
+                            task.difficulty = difficulty;
+                            format!("changed difficulty of '{}'", task.title)
+                        };
+
+                        let description = tasks_view.modify_selected_task(state, config, f);
+                        describe(state, description);
                         UiState::TasksView(tasks_view)
                     }
                 }
-                difficulty::Response::SetDifficulty(difficulty) => {
-                    tasks_view
-                        .modify_selected_task(state, config, |task| task.difficulty = difficulty);
-                    UiState::TasksView(tasks_view)
-                }
-            },
+            }
             UiState::TasksPrompt(tasks_view, TasksPrompt::DueDatePrompt(prompt)) => {
                 match prompt.on_key(key) {
                     due_date::Response::Update(prompt) => {
                         UiState::TasksPrompt(tasks_view, prompt.into())
                     }
                     due_date::Response::SetDueDate(date) => {
-                        tasks_view
-                            .modify_selected_task(state, config, |task| task.set_due_date(date));
+                        let f = |task: &mut Task| {
+                            task.set_due_date(date);
+                            format!("changed due date of '{}'", task.title)
+                        };
+
+                        let description = tasks_view.modify_selected_task(state, config, f);
+                        describe(state, description);
                         UiState::TasksView(tasks_view)
                     }
                     due_date::Response::ModifyCurrentlyCreatedTask(task) => {
-                        tasks_view.push_task(task, state);
-                        UiState::TasksView(tasks_view)
+                        return push_task(tasks_view, task, state, config);
                     }
                 }
             }
             UiState::TasksPrompt(tasks_view, TasksPrompt::MoveToColumnPrompt(prompt)) => {
                 match prompt.on_key(key, config) {
                     move_to_column::Response::MoveToColumn(column) => {
-                        if let Some(task) = tasks_view.delete_selected_task(state, config) {
-                            let f =
-                                |project: &mut Project| project.columns.get_mut(column).push(task);
-                            tasks_view.modify_selected_project(state, f);
-                        }
+                        let project = tasks_view.project();
+                        let from = Some((project, tasks_view.focused_column().name.as_str()));
+                        let message = match check_wip_limit(project, column, from, state, config) {
+                            Ok(warning) => {
+                                let description =
+                                    tasks_view.move_selected_task(state, config, column);
+                                describe(state, description);
+                                warning
+                            }
+                            Err(refusal) => Some(format!("{refusal}, the task wasn't moved"))
+                        };
 
-                        UiState::TasksView(tasks_view)
+                        return Response::Update(Ui(UiState::TasksView(tasks_view), message));
                     }
                     move_to_column::Response::Update(move_to_column_prompt) => {
                         UiState::TasksPrompt(tasks_view, move_to_column_prompt.into())
                     }
                 }
             }
+            UiState::TasksPrompt(tasks_view, TasksPrompt::MoveToProjectPrompt(prompt)) => {
+                match prompt.on_key(key, state, config) {
+                    move_to_project::Response::Update(prompt) => {
+                        UiState::TasksPrompt(tasks_view, prompt.into())
+                    }
+                    move_to_project::Response::Transfer(transfer) => {
+                        let message =
+                            tasks_view
+                                .selected_task(state, config)
+                                .and_then(|(column, task)| {
+                                    let project = tasks_view.project();
+                                    transfer_task(project, column, task, transfer, state, config)
+                                });
+
+                        return Response::Update(Ui(UiState::TasksView(tasks_view), message));
+                    }
+                }
+            }
+            UiState::TasksPrompt(tasks_view, TasksPrompt::NotesPrompt(prompt)) => {
+                match prompt.on_key(key) {
+                    notes::Response::Update(prompt) => {
+                        UiState::TasksPrompt(tasks_view, prompt.into())
+                    }
+                    notes::Response::Save(description) => {
+                        let f = |task: &mut Task| {
+                            task.description = description;
+                            format!("edited notes of '{}'", task.title)
+                        };
+
+                        let description = tasks_view.modify_selected_task(state, config, f);
+                        describe(state, description);
+                        UiState::TasksView(tasks_view)
+                    }
+                }
+            }
+            UiState::TasksPrompt(tasks_view, TasksPrompt::ChecklistPrompt(prompt)) => {
+                match prompt.on_key(key, state, config) {
+                    checklist::Response::Update(prompt) => {
+                        UiState::TasksPrompt(tasks_view, prompt.into())
+                    }
+                    checklist::Response::Modify(prompt, action) => {
+                        let f = |task: &mut Task| action.apply(task);
+                        let description = tasks_view.modify_selected_task(state, config, f);
+                        describe(state, description.flatten());
+                        UiState::TasksPrompt(tasks_view, prompt.into())
+                    }
+                }
+            }
+            UiState::TasksPrompt(mut tasks_view, TasksPrompt::TagsPrompt(prompt)) => {
+                match prompt.on_key(key, config) {
+                    tags::Response::Update(prompt) => {
+                        UiState::TasksPrompt(tasks_view, prompt.into())
+                    }
+                    tags::Response::Submit(TagsAction::SetTags, tags) => {
+                        let f = |task: &mut Task| {
+                            task.tags = tags;
+                            format!("changed tags of '{}'", task.title)
+                        };
+
+                        let description = tasks_view.modify_selected_task(state, config, f);
+                        describe(state, description);
+                        UiState::TasksView(tasks_view)
+                    }
+                    tags::Response::Submit(TagsAction::Filter, tags) => {
+                        tasks_view.set_tag_filter(tags);
+                        UiState::TasksView(tasks_view)
+                    }
+                }
+            }
+            UiState::TasksPrompt(tasks_view, TasksPrompt::BlockersPrompt(prompt)) => {
+                match prompt.on_key(key, state, config) {
+                    blockers::Response::Update(prompt) => {
+                        UiState::TasksPrompt(tasks_view, prompt.into())
+                    }
+                    blockers::Response::Submit(blocked_by) => {
+                        let f = |task: &mut Task| {
+                            task.blocked_by = blocked_by;
+                            format!("changed blockers of '{}'", task.title)
+                        };
+
+                        let description = tasks_view.modify_selected_task(state, config, f);
+                        describe(state, description);
+                        UiState::TasksView(tasks_view)
+                    }
+                }
+            }
+            UiState::TasksPrompt(tasks_view, TasksPrompt::TaskDetailPrompt(prompt)) => {
+                UiState::TasksPrompt(tasks_view, prompt.on_key(key).into())
+            }
+            UiState::TasksPrompt(tasks_view, TasksPrompt::RecurrencePrompt(prompt)) => {
+                match prompt.on_key(key) {
+                    recurrence::Response::Update(prompt) => {
+                        UiState::TasksPrompt(tasks_view, prompt.into())
+                    }
+                    recurrence::Response::SetRecurrence(recurrence) => {
+                        let f = |task: &mut Task| {
+                            task.recurrence = recurrence;
+                            format!("changed recurrence of '{}'", task.title)
+                        };
+
+                        let description = tasks_view.modify_selected_task(state, config, f);
+                        describe(state, description);
+                        UiState::TasksView(tasks_view)
+                    }
+                }
+            }
             UiState::TasksPrompt(tasks_view, TasksPrompt::TaskDeleteConfirmation(prompt)) => {
                 match prompt.on_key(key) {
                     delete::Response::Delete => {
-                        tasks_view.delete_selected_task(state, config);
+                        let task = tasks_view.delete_selected_task(state, config);
+                        let description = task.map(|task| format!("deleted task '{}'", task.title));
+                        describe(state, description);
                         UiState::TasksView(tasks_view)
                     }
                     delete::Response::Update(prompt) => {
@@ -305,4 +758,114 @@ impl<'a> Ui<'a> {
         }
         .into()
     }
+
+    fn undo_or_redo(view: UiState<'a>, key: KeyEvent, state: &mut State) -> Response<'a> {
+        let message = match key.code {
+            KeyCode::Char('u') => state
+                .undo()
+                .map(|description| format!("undone: {description}"))
+                .unwrap_or("Nothing to undo".to_string()),
+            _ => state
+                .redo()
+                .map(|description| format!("redone: {description}"))
+                .unwrap_or("Nothing to redo".to_string())
+        };
+
+        let view = match view {
+            UiState::TasksView(tasks_view) if state.project(tasks_view.project()).is_none() => {
+                UiState::MainView(
+                    ProjectsView::default(),
+                    DueTasksView::default(),
+                    MainViewFocus::Projects
+                )
+            }
+            view => view
+        };
+
+        Response::Update(Ui(view, Some(message)))
+    }
+}
+
+/// Adds the task to the focused column unless its WIP limit refuses it
+fn push_task<'a>(
+    tasks_view: TasksView<'a>,
+    task: Task,
+    state: &mut State,
+    config: &Config
+) -> Response<'a> {
+    let column = tasks_view.focused_column();
+    let message = match check_wip_limit(tasks_view.project(), column, None, state, config) {
+        Ok(warning) => {
+            tasks_view.push_task(task, state);
+            warning
+        }
+        Err(refusal) => Some(format!("{refusal}, the task wasn't created"))
+    };
+
+    Response::Update(Ui(UiState::TasksView(tasks_view), message))
+}
+
+/// Moves or copies the task to a column of a project unless the WIP limit of the column refuses it,
+/// returns the message to show
+fn transfer_task(
+    from: ProjectId,
+    column: &str,
+    task: TaskId,
+    transfer: Transfer,
+    state: &mut State,
+    config: &Config
+) -> Option<String> {
+    let Transfer {
+        project: to,
+        column: to_column,
+        copy
+    } = transfer;
+    let moved_from = (!copy).then_some((from, column));
+    match check_wip_limit(to, to_column, moved_from, state, config) {
+        Ok(warning) => {
+            let description = match copy {
+                true => state.copy_task_to_project(from, column, task, to, to_column, config),
+                false => state.move_task_to_project(from, column, task, to, to_column, config)
+            };
+            describe(state, description);
+            warning
+        }
+        Err(refusal) => {
+            let verb = if copy { "copied" } else { "moved" };
+            Some(format!("{refusal}, the task wasn't {verb}"))
+        }
+    }
+}
+
+/// Errors with the reason if adding a task to the column is refused because of its WIP limit,
+/// otherwise returns the warning to show if the limit is exceeded. `from` is the project and column
+/// a moved task comes from, a task moved to the column it's in doesn't add to it
+fn check_wip_limit(
+    project: ProjectId,
+    column: &ColumnConfig,
+    from: Option<(ProjectId, &str)>,
+    state: &State,
+    config: &Config
+) -> Result<Option<String>, String> {
+    if from == Some((project, column.name.as_str())) {
+        return Ok(None);
+    }
+
+    let project = state.project(project);
+    match column.wip_limit {
+        Some(limit) if project.is_some_and(|project| project.is_at_wip_limit(column)) => {
+            let message = format!("{} is at its WIP limit of {limit}", column.name);
+            match config.wip_limit_policy {
+                WipLimitPolicy::Warn => Ok(Some(message)),
+                WipLimitPolicy::Refuse => Err(message)
+            }
+        }
+        _ => Ok(None)
+    }
+}
+
+fn describe(state: &mut State, description: Option<String>) {
+    if let Some(description) = description {
+        state.describe_change(description)
+    }
 }
@@ -1,4 +1,7 @@
-use std::{fmt::Debug, ops::Deref};
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut}
+};
 
 use kraban_config::Config;
 use kraban_state::State;
@@ -55,6 +58,10 @@ impl<Q> Deref for List<Q> {
     fn deref(&self) -> &Self::Target { &self.query }
 }
 
+impl<Q> DerefMut for List<Q> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.query }
+}
+
 impl<Q> List<Q> {
     pub fn new(query: Q) -> Self {
         Self {
@@ -64,6 +71,8 @@ impl<Q> List<Q> {
     }
 
     pub fn selected(&self) -> usize { self.selected.selected().unwrap() }
+    pub fn select(&mut self, idx: usize) { self.selected.select(Some(idx)) }
+    pub fn into_query(self) -> Q { self.query }
 }
 
 impl<Q: ListQuery> Keyhints for List<Q> {
@@ -1,6 +1,8 @@
-use itertools::Itertools;
+use std::iter;
+
+use itertools::{Itertools, chain};
 use kraban_config::{ColumnConfig, Config};
-use kraban_state::{Project, State};
+use kraban_state::{Identifiable, Project, ProjectId, State};
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
@@ -15,7 +17,8 @@ use crate::{
         ProjectsPrompt,
         delete::ProjectDeleteConfirmation,
         input::{InputAction, InputPrompt},
-        priority::PriorityPrompt
+        priority::PriorityPrompt,
+        templates::TemplatesPrompt
     },
     table::{Table, TableQuery, table},
     task::TasksView,
@@ -27,6 +30,12 @@ pub enum Response<'a> {
     OpenPrompt(ProjectsView, ProjectsPrompt),
     SwitchToTasksView(TasksView<'a>),
     SwitchToDueTasksView(ProjectsView),
+    SwitchToArchiveView,
+    SwitchToTrashView,
+    SwitchToFlowView,
+    OpenBoardSwitcher(ProjectsView),
+    ArchiveSelected(ProjectsView),
+    DuplicateSelected(ProjectsView),
     Update(ProjectsView)
 }
 
@@ -34,10 +43,13 @@ pub enum Response<'a> {
 pub struct ProjectsView(table!(ProjectsTableQuery));
 impl Keyhints for ProjectsView {
     fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
-        const ITEM_REQUIRING_KEYHINTS: [(&str, &str); 4] = [
+        const ITEM_REQUIRING_KEYHINTS: [(&str, &str); 7] = [
             ("Delete/Backspace", "Delete"),
+            ("a", "Archive"),
             ("p", "Set priority"),
             ("r", "Rename"),
+            ("y", "Duplicate"),
+            ("s", "Save as template"),
             ("Enter", "View project tasks")
         ];
 
@@ -51,25 +63,41 @@ impl Keyhints for ProjectsView {
             .then_some(item_requiring_iter)
             .into_iter()
             .flatten()
-            .chain([("n", "New"), ("Tab", "Switch to due tasks view")])
+            .chain([
+                ("n", "New"),
+                ("c", "New from template"),
+                ("v", "View archive"),
+                ("t", "View trash"),
+                ("f", "View flow report"),
+                ("b", "Switch board"),
+                ("Tab", "Switch to due tasks view")
+            ])
     }
 }
 
 impl ProjectsView {
-    pub fn new(idx: usize) -> Self { Self(Table::new(idx, ProjectsTableQuery)) }
+    pub fn new(project: ProjectId) -> Self { Self(Table::new(Some(project), ProjectsTableQuery)) }
+    pub fn selected_id(&self, state: &State, config: &Config) -> Option<ProjectId> {
+        self.0.selected_id(state, config)
+    }
+
     pub fn modify_selected_project<T>(
         &self,
         f: impl FnOnce(&mut Project) -> T,
         state: &mut State,
         config: &Config
     ) -> Option<T> {
-        let idx = self.0.selected(state, config)?;
-        Some(state.projects_mut().modify_item_at(idx, f))
+        let id = self.0.selected_id(state, config)?;
+        state.projects_mut().modify_item_with_id(id, f)
     }
 
-    pub fn delete_selected_project(&self, state: &mut State, config: &Config) -> Option<Project> {
-        let idx = self.0.selected(state, config)?;
-        Some(state.projects_mut().remove(idx))
+    pub fn delete_selected_project<'s>(
+        &self,
+        state: &'s mut State,
+        config: &Config
+    ) -> Option<&'s Project> {
+        let id = self.0.selected_id(state, config)?;
+        state.trash_project(id)
     }
 
     pub fn render(
@@ -85,21 +113,36 @@ impl ProjectsView {
 
     pub fn on_key<'a>(mut self, key: KeyEvent, state: &State, config: &'a Config) -> Response<'a> {
         const NONE: KeyModifiers = KeyModifiers::NONE;
-        match (key.code, key.modifiers, self.0.selected(state, config)) {
-            (KeyCode::Delete | KeyCode::Backspace, NONE, Some(idx)) => {
-                self.prompt(ProjectDeleteConfirmation::new(idx))
+        let selected = self
+            .0
+            .selected_id(state, config)
+            .and_then(|id| state.project(id));
+        match (key.code, key.modifiers, selected) {
+            (KeyCode::Delete | KeyCode::Backspace, NONE, Some(project)) => {
+                self.prompt(ProjectDeleteConfirmation::new(project.id()))
             }
             (KeyCode::Char('n'), NONE, _) => {
                 let placeholder = "Enter new project name".to_string();
                 self.prompt(InputPrompt::new(config, InputAction::New, placeholder))
             }
             (KeyCode::Char('p'), NONE, Some(_)) => self.prompt(PriorityPrompt::new(None)),
-            (KeyCode::Char('r'), NONE, Some(idx)) => {
-                let text = state.projects()[idx].title.clone();
+            (KeyCode::Char('a'), NONE, Some(_)) => Response::ArchiveSelected(self),
+            (KeyCode::Char('y'), NONE, Some(_)) => Response::DuplicateSelected(self),
+            (KeyCode::Char('s'), NONE, Some(project)) => {
+                let text = project.title.clone();
+                self.prompt(InputPrompt::new(config, InputAction::SaveTemplate, text))
+            }
+            (KeyCode::Char('c'), NONE, _) => self.prompt(TemplatesPrompt::new()),
+            (KeyCode::Char('v'), NONE, _) => Response::SwitchToArchiveView,
+            (KeyCode::Char('t'), NONE, _) => Response::SwitchToTrashView,
+            (KeyCode::Char('f'), NONE, _) => Response::SwitchToFlowView,
+            (KeyCode::Char('b'), NONE, _) => Response::OpenBoardSwitcher(self),
+            (KeyCode::Char('r'), NONE, Some(project)) => {
+                let text = project.title.clone();
                 self.prompt(InputPrompt::new(config, InputAction::Rename, text))
             }
-            (KeyCode::Enter, NONE, Some(idx)) => {
-                Response::SwitchToTasksView(TasksView::new(idx, config))
+            (KeyCode::Enter, NONE, Some(project)) => {
+                Response::SwitchToTasksView(TasksView::new(project.id(), config))
             }
             (KeyCode::Tab, NONE, _) => Response::SwitchToDueTasksView(self),
             _ => {
@@ -109,34 +152,63 @@ impl ProjectsView {
         }
     }
 
-    pub fn prompt<T: Into<ProjectsPrompt>>(self, prompt: T) -> Response<'static> {
+    pub fn prompt<'a, T: Into<ProjectsPrompt>>(self, prompt: T) -> Response<'a> {
         Response::OpenPrompt(self, prompt.into())
     }
 }
 
 #[derive(Default)]
 struct ProjectsTableQuery;
+impl ProjectsTableQuery {
+    fn projects<'a>(&self, state: &'a State, config: &Config) -> impl Iterator<Item = &'a Project> {
+        state
+            .sorted_projects(config)
+            .filter(|project| !project.archived)
+    }
+}
+
 impl TableQuery<3> for ProjectsTableQuery {
+    type Id = ProjectId;
+    fn ids<'a>(&self, state: &'a State, config: &'a Config) -> impl Iterator<Item = ProjectId> {
+        self.projects(state, config).map(Project::id)
+    }
+
     fn rows<'a>(
         &self,
         state: &'a State,
         config: &'a Config
     ) -> impl Iterator<Item = [Line<'a>; 3]> {
-        state.projects().iter().map(move |project| {
-            [
-                project.priority.map(priority_to_line).unwrap_or_default(),
-                Line::from_iter(tasks_count_hint(config, project)),
-                project.title.as_str().into()
-            ]
-        })
+        self.projects(state, config)
+            .map(move |project| project_row(project, config))
     }
 
-    fn len(&self, state: &State, _: &Config) -> usize { state.projects().len() }
-    const CONSTRAINTS: [Constraint; 3] = [
-        PRIORITY_CONSTRAINT,
-        Constraint::Fill(1),
-        Constraint::Fill(1)
-    ];
+    fn len(&self, state: &State, config: &Config) -> usize { self.projects(state, config).count() }
+    const CONSTRAINTS: [Constraint; 3] = PROJECT_CONSTRAINTS;
+}
+
+pub const PROJECT_CONSTRAINTS: [Constraint; 3] = [
+    PRIORITY_CONSTRAINT,
+    Constraint::Fill(1),
+    Constraint::Fill(1)
+];
+
+/// Projects with a column over its WIP limit are flagged
+pub fn project_row<'a>(project: &'a Project, config: &'a Config) -> [Line<'a>; 3] {
+    let over_wip_limit = config
+        .column_configs()
+        .any(|column| project.is_over_wip_limit(column));
+    [
+        project
+            .priority
+            .as_ref()
+            .map(|priority| priority_to_line(priority, config))
+            .unwrap_or_default(),
+        Line::from_iter(tasks_count_hint(config, project)),
+        Line::from_iter(chain![
+            iter::once(project.title.as_str().into()),
+            over_wip_limit.then_some(" ⚠ over WIP limit".red())
+        ])
+    ]
 }
 
 #[allow(unstable_name_collisions)]
@@ -0,0 +1,154 @@
+use itertools::chain;
+use kraban_config::{ColumnConfig, Config};
+use kraban_state::{Identifiable, Project, State, Task, TaskId};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::Widget
+};
+
+use super::Prompt;
+use crate::{
+    keyhints::Keyhints,
+    list::{List, ListQuery}
+};
+
+pub struct BlockersPrompt(List<BlockersQuery>);
+
+struct BlockersQuery {
+    task: TaskId,
+    selected: Vec<TaskId>
+}
+
+pub enum Response {
+    Update(BlockersPrompt),
+    Submit(Vec<TaskId>)
+}
+
+impl BlockersQuery {
+    /// Tasks of every unarchived project that can block the task, ordered by project and column
+    fn candidates<'a>(
+        &self,
+        state: &'a State,
+        config: &'a Config
+    ) -> impl Iterator<Item = (&'a Project, &'a ColumnConfig, &'a Task)> {
+        let task = self.task;
+        let projects = state.projects().iter().filter(|project| !project.archived);
+        projects.flat_map(move |project| {
+            config.column_configs().flat_map(move |column| {
+                project
+                    .columns
+                    .get(&column.name)
+                    .iter()
+                    .filter(move |candidate| candidate.id() != task)
+                    .map(move |candidate| (project, column, candidate))
+            })
+        })
+    }
+
+    fn toggle(&mut self, id: TaskId) {
+        match self.selected.iter().position(|selected| *selected == id) {
+            Some(idx) => {
+                self.selected.remove(idx);
+            }
+            None => self.selected.push(id)
+        }
+    }
+}
+
+impl BlockersPrompt {
+    pub fn new(task: &Task) -> Self {
+        Self(List::new(BlockersQuery {
+            task: task.id(),
+            selected: task.blocked_by.clone()
+        }))
+    }
+
+    pub fn on_key(mut self, key: KeyEvent, state: &State, config: &Config) -> Response {
+        let candidates_len = self.0.candidates(state, config).count();
+        let selected = (candidates_len != 0).then(|| self.0.selected().min(candidates_len - 1));
+        match (key.code, key.modifiers, selected) {
+            (KeyCode::Char(' '), KeyModifiers::NONE, Some(idx)) => {
+                let (_, _, task) = self.0.candidates(state, config).nth(idx).unwrap();
+                let id = task.id();
+                self.0.toggle(id);
+                Response::Update(self)
+            }
+            (KeyCode::Enter, KeyModifiers::NONE, _) => {
+                // Blockers deleted in the meantime are dropped
+                let mut selected = self.0.into_query().selected;
+                selected.retain(|id| state.find_task(*id).is_some());
+                Response::Submit(selected)
+            }
+            _ => {
+                self.0.on_key(key);
+                Response::Update(self)
+            }
+        }
+    }
+}
+
+impl ListQuery for BlockersQuery {
+    fn get_items<'a>(
+        &self,
+        state: &'a State,
+        config: &'a Config
+    ) -> impl Iterator<Item = Line<'a>> {
+        let items = self
+            .candidates(state, config)
+            .map(|(project, column, task)| {
+                let checkbox = match self.selected.contains(&task.id()) {
+                    true => "[x] ",
+                    false => "[ ] "
+                };
+
+                let title = match task.completed_at() {
+                    Some(_) => task.title.as_str().crossed_out().dark_gray(),
+                    None => task.title.as_str().into()
+                };
+
+                Line::from_iter([
+                    checkbox.fg(config.app_color),
+                    project.title.as_str().dark_gray(),
+                    format!(" #{} ", task.number()).dark_gray(),
+                    title,
+                    format!(" {}", column.name).fg(column.color).italic()
+                ])
+            });
+
+        Vec::from_iter(items).into_iter()
+    }
+}
+
+impl Prompt for BlockersPrompt {
+    fn height(&self, state: &State, config: &Config) -> u16 {
+        const MAX_TASKS: u16 = 15;
+        (self.0.candidates(state, config).count() as u16).clamp(1, MAX_TASKS)
+    }
+
+    fn width(&self) -> u16 { 80 }
+    fn title(&self) -> &'static str { "Blocked by" }
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
+        if self.0.candidates(state, config).next().is_none() {
+            Line::from("No other tasks".italic().dark_gray()).render(area, buf);
+        } else {
+            self.0.render(area, buf, state, config);
+        }
+    }
+}
+
+impl Keyhints for BlockersPrompt {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        let has_candidates = self.0.candidates(state, config).next().is_some();
+        chain![
+            has_candidates
+                .then(|| chain![self.0.keyhints(state, config), [("Space", "Toggle")]])
+                .into_iter()
+                .flatten(),
+            [("Enter", "Save")]
+        ]
+    }
+}
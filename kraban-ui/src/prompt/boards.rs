@@ -0,0 +1,86 @@
+use kraban_config::{Board, Config};
+use kraban_state::State;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::Stylize,
+    text::Line
+};
+
+use super::Prompt;
+use crate::{
+    keyhints::Keyhints,
+    list::{List, ListQuery}
+};
+
+pub struct BoardsPrompt(List<BoardsQuery>);
+
+struct BoardsQuery {
+    boards: Vec<Board>
+}
+
+pub enum Response {
+    Update(BoardsPrompt),
+    Switch(Board)
+}
+
+impl BoardsPrompt {
+    pub fn new(boards: Vec<Board>, config: &Config) -> Self {
+        let mut list = List::new(BoardsQuery { boards });
+        let current = list.boards.iter().position(|board| *board == config.board);
+        list.select(current.unwrap_or_default());
+        Self(list)
+    }
+
+    pub fn on_key(mut self, key: KeyEvent) -> Response {
+        match (key.code, key.modifiers) {
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                let selected = self.0.selected();
+                Response::Switch(self.0.into_query().boards.swap_remove(selected))
+            }
+            _ => {
+                self.0.on_key(key);
+                Response::Update(self)
+            }
+        }
+    }
+}
+
+impl ListQuery for BoardsQuery {
+    fn get_items<'a>(&self, _: &'a State, config: &'a Config) -> impl Iterator<Item = Line<'a>> {
+        let items = self
+            .boards
+            .iter()
+            .map(|board| match *board == config.board {
+                true => Line::from_iter([
+                    board.to_string().fg(config.app_color),
+                    " (current)".italic()
+                ]),
+                false => Line::from(board.to_string())
+            });
+
+        Vec::from_iter(items).into_iter()
+    }
+}
+
+impl Prompt for BoardsPrompt {
+    fn height(&self, _: &State, _: &Config) -> u16 {
+        const MAX_BOARDS: u16 = 15;
+        (self.0.boards.len() as u16).clamp(1, MAX_BOARDS)
+    }
+
+    fn title(&self) -> &'static str { "Switch board" }
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
+        self.0.render(area, buf, state, config);
+    }
+}
+
+impl Keyhints for BoardsPrompt {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        self.0
+            .keyhints(state, config)
+            .into_iter()
+            .chain([("Enter", "Switch")])
+    }
+}
@@ -0,0 +1,219 @@
+use itertools::chain;
+use kraban_config::Config;
+use kraban_state::{ChecklistItem, ProjectId, State, Task, TaskId};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::Widget
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use super::Prompt;
+use crate::{
+    keyhints::Keyhints,
+    list::{List, ListQuery}
+};
+
+pub struct ChecklistPrompt<'a> {
+    list: List<ChecklistQuery<'a>>,
+    editing: Option<(Editing, TextArea<'static>)>
+}
+
+#[derive(Clone, Copy)]
+enum Editing {
+    New,
+    Rename(usize)
+}
+
+struct ChecklistQuery<'a> {
+    project: ProjectId,
+    column: &'a str,
+    task: TaskId
+}
+
+pub enum Response<'a> {
+    Update(ChecklistPrompt<'a>),
+    Modify(ChecklistPrompt<'a>, ChecklistAction)
+}
+
+pub enum ChecklistAction {
+    Toggle(usize),
+    Add(String),
+    Rename(usize, String),
+    Delete(usize)
+}
+
+impl ChecklistAction {
+    /// Returns the description of the change
+    pub fn apply(self, task: &mut Task) -> Option<String> {
+        let description = match self {
+            ChecklistAction::Toggle(idx) => {
+                let item = task.checklist.get_mut(idx)?;
+                item.done = !item.done;
+                format!("toggled '{}' in '{}'", item.title, task.title)
+            }
+            ChecklistAction::Add(title) => {
+                let description = format!("added '{title}' to '{}'", task.title);
+                task.checklist.push(ChecklistItem::new(title));
+                description
+            }
+            ChecklistAction::Rename(idx, title) => {
+                let item = task.checklist.get_mut(idx)?;
+                let description = format!("renamed '{}' to '{title}'", item.title);
+                item.title = title;
+                description
+            }
+            ChecklistAction::Delete(idx) => {
+                (idx < task.checklist.len()).then_some(())?;
+                let item = task.checklist.remove(idx);
+                format!("deleted '{}' from '{}'", item.title, task.title)
+            }
+        };
+
+        Some(description)
+    }
+}
+
+impl ChecklistQuery<'_> {
+    fn checklist<'a>(&self, state: &'a State) -> &'a [ChecklistItem] {
+        state
+            .project(self.project)
+            .and_then(|project| project.columns.get(self.column).get_by_id(self.task))
+            .map(|task| task.checklist.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+impl<'a> ChecklistPrompt<'a> {
+    pub fn new(project: ProjectId, column: &'a str, task: TaskId) -> Self {
+        Self {
+            list: List::new(ChecklistQuery {
+                project,
+                column,
+                task
+            }),
+            editing: None
+        }
+    }
+
+    pub fn on_key(mut self, key: KeyEvent, state: &State, config: &Config) -> Response<'a> {
+        if let Some((editing, mut text_area)) = self.editing.take() {
+            if (key.code, key.modifiers) != (KeyCode::Enter, KeyModifiers::NONE) {
+                text_area.input(key);
+                self.editing = Some((editing, text_area));
+                return Response::Update(self);
+            }
+
+            let title = text_area.into_lines().remove(0);
+            let action = match editing {
+                Editing::New => ChecklistAction::Add(title),
+                Editing::Rename(idx) => ChecklistAction::Rename(idx, title)
+            };
+
+            return Response::Modify(self, action);
+        }
+
+        let checklist = self.list.checklist(state);
+        let selected =
+            (!checklist.is_empty()).then(|| self.list.selected().min(checklist.len() - 1));
+        match (key.code, key.modifiers, selected) {
+            (KeyCode::Char(' '), KeyModifiers::NONE, Some(idx)) => {
+                Response::Modify(self, ChecklistAction::Toggle(idx))
+            }
+            (KeyCode::Delete | KeyCode::Backspace, KeyModifiers::NONE, Some(idx)) => {
+                Response::Modify(self, ChecklistAction::Delete(idx))
+            }
+            (KeyCode::Char('n'), KeyModifiers::NONE, _) => {
+                let mut text_area = text_area(config);
+                text_area.set_placeholder_text("Enter new item name");
+                self.editing = Some((Editing::New, text_area));
+                Response::Update(self)
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE, Some(idx)) => {
+                let mut text_area = text_area(config);
+                text_area.insert_str(&checklist[idx].title);
+                text_area.move_cursor(CursorMove::End);
+                self.editing = Some((Editing::Rename(idx), text_area));
+                Response::Update(self)
+            }
+            _ => {
+                self.list.on_key(key);
+                Response::Update(self)
+            }
+        }
+    }
+}
+
+fn text_area(config: &Config) -> TextArea<'static> {
+    let mut text_area = TextArea::default();
+    text_area.set_selection_style(Style::new().fg(config.app_color).reversed());
+    text_area.set_cursor_line_style(Style::new());
+    text_area
+}
+
+impl ListQuery for ChecklistQuery<'_> {
+    fn get_items<'a>(
+        &self,
+        state: &'a State,
+        config: &'a Config
+    ) -> impl Iterator<Item = Line<'a>> {
+        self.checklist(state).iter().map(|item| match item.done {
+            true => Line::from_iter([
+                "[x] ".fg(config.app_color),
+                item.title.as_str().crossed_out().dark_gray()
+            ]),
+            false => Line::from_iter(["[ ] ".fg(config.app_color), item.title.as_str().into()])
+        })
+    }
+}
+
+impl Prompt for ChecklistPrompt<'_> {
+    fn height(&self, state: &State, _: &Config) -> u16 {
+        const MAX_ITEMS: u16 = 15;
+        let items = (self.list.checklist(state).len() as u16).clamp(1, MAX_ITEMS);
+        items + self.editing.is_some() as u16
+    }
+
+    fn title(&self) -> &'static str { "Checklist" }
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
+        let [list_area, input_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(self.editing.is_some() as u16)
+        ])
+        .areas(area);
+
+        if self.list.checklist(state).is_empty() {
+            Line::from("No items yet".italic().dark_gray()).render(list_area, buf);
+        } else {
+            self.list.render(list_area, buf, state, config);
+        }
+
+        if let Some((_, text_area)) = &self.editing {
+            text_area.render(input_area, buf);
+        }
+    }
+}
+
+impl Keyhints for ChecklistPrompt<'_> {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        const ITEM_REQUIRING_KEYHINTS: [(&str, &str); 3] = [
+            ("Space", "Toggle"),
+            ("r", "Rename"),
+            ("Delete/Backspace", "Delete")
+        ];
+
+        let editing = self.editing.is_some();
+        let has_items = !self.list.checklist(state).is_empty();
+        chain![
+            editing.then_some(("Enter", "Submit")),
+            (!editing && has_items)
+                .then(|| chain![self.list.keyhints(state, config), ITEM_REQUIRING_KEYHINTS])
+                .into_iter()
+                .flatten(),
+            (!editing).then_some(("n", "New"))
+        ]
+    }
+}
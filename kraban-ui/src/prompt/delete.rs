@@ -1,5 +1,5 @@
 use kraban_config::Config;
-use kraban_state::State;
+use kraban_state::{ProjectId, State, TaskId};
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
@@ -13,9 +13,9 @@ use super::Prompt;
 use crate::keyhints::Keyhints;
 
 pub struct TaskDeleteConfirmation<'a> {
-    project_idx: usize,
+    project: ProjectId,
     column: &'a str,
-    task_idx: usize
+    task: TaskId
 }
 
 pub enum Response<T> {
@@ -24,11 +24,11 @@ pub enum Response<T> {
 }
 
 impl<'a> TaskDeleteConfirmation<'a> {
-    pub fn new(project_idx: usize, column: &'a str, task_idx: usize) -> Self {
+    pub fn new(project: ProjectId, column: &'a str, task: TaskId) -> Self {
         Self {
-            project_idx,
+            project,
             column,
-            task_idx
+            task
         }
     }
 
@@ -47,11 +47,11 @@ impl<'a> TaskDeleteConfirmation<'a> {
 }
 
 pub struct ProjectDeleteConfirmation {
-    project_idx: usize
+    project: ProjectId
 }
 
 impl ProjectDeleteConfirmation {
-    pub fn new(project_idx: usize) -> Self { Self { project_idx } }
+    pub fn new(project: ProjectId) -> Self { Self { project } }
     pub fn on_key(self, key: KeyEvent) -> Response<Self> {
         if let KeyEvent {
             code: KeyCode::Enter | KeyCode::Char('Y') | KeyCode::Char('y'),
@@ -70,9 +70,11 @@ impl Prompt for TaskDeleteConfirmation<'_> {
     fn height(&self, _: &State, _: &Config) -> u16 { 1 }
     fn title(&self) -> &'static str { "Delete task" }
     fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
-        let task_name = state.projects()[self.project_idx].columns.get(self.column)[self.task_idx]
-            .title
-            .as_str()
+        let task_name = state
+            .project(self.project)
+            .and_then(|project| project.columns.get(self.column).get_by_id(self.task))
+            .map(|task| task.title.as_str())
+            .unwrap_or_default()
             .fg(config.app_color)
             .italic();
 
@@ -85,9 +87,10 @@ impl Prompt for ProjectDeleteConfirmation {
     fn height(&self, _: &State, _: &Config) -> u16 { 1 }
     fn title(&self) -> &'static str { "Delete project" }
     fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
-        let project_name = state.projects()[self.project_idx]
-            .title
-            .as_str()
+        let project_name = state
+            .project(self.project)
+            .map(|project| project.title.as_str())
+            .unwrap_or_default()
             .fg(config.app_color)
             .italic();
 
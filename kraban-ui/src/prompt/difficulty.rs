@@ -10,13 +10,11 @@ use ratatui::{
     style::Stylize,
     text::Line
 };
-use strum::{EnumCount, IntoEnumIterator};
 
 use crate::{
     keyhints::Keyhints,
     list::{List, ListQuery},
-    prompt::Prompt,
-    utils::difficulty_to_color
+    prompt::Prompt
 };
 
 pub enum Response {
@@ -39,12 +37,15 @@ impl DifficultyPrompt {
         }
     }
 
-    pub fn on_key(self, key: KeyEvent) -> Response {
+    pub fn on_key(self, key: KeyEvent, config: &Config) -> Response {
         const NONE: KeyModifiers = KeyModifiers::NONE;
         match (key.code, self.currently_creating, self.list, key.modifiers) {
             (KeyCode::Enter, currently_creating, list, NONE) => {
-                let priority = Difficulty::iter().nth(list.selected());
-                Self::difficulty_selected(currently_creating, priority)
+                let difficulty = config
+                    .difficulties
+                    .get(list.selected())
+                    .map(|level| Difficulty::new(level.name.clone()));
+                Self::difficulty_selected(currently_creating, difficulty)
             }
             (KeyCode::Backspace, currenty_creating, _, NONE) => {
                 Self::difficulty_selected(currenty_creating, None)
@@ -74,7 +75,7 @@ impl DifficultyPrompt {
 }
 
 impl Prompt for DifficultyPrompt {
-    fn height(&self, _: &State, _: &Config) -> u16 { Difficulty::COUNT as u16 }
+    fn height(&self, _: &State, config: &Config) -> u16 { config.difficulties.len() as u16 }
     fn title(&self) -> &'static str { "Change difficulty" }
     fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
         self.list.render(area, buf, state, config);
@@ -93,10 +94,10 @@ impl Keyhints for DifficultyPrompt {
 }
 
 impl ListQuery for DifficultyListQuery {
-    fn get_items<'a>(&self, _: &'a State, _: &'a Config) -> impl Iterator<Item = Line<'a>> {
-        Difficulty::iter().map(|difficulty| {
-            let str: &str = difficulty.into();
-            Line::raw(str).fg(difficulty_to_color(difficulty))
-        })
+    fn get_items<'a>(&self, _: &'a State, config: &'a Config) -> impl Iterator<Item = Line<'a>> {
+        config
+            .difficulties
+            .iter()
+            .map(|level| Line::raw(&level.name).fg(level.color))
     }
 }
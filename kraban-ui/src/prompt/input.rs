@@ -21,20 +21,25 @@ pub struct InputPrompt {
 #[derive(strum_macros::Display, Debug)]
 pub enum InputAction {
     Rename,
-    New
+    New,
+    SaveTemplate,
+    /// Names the project created from the template
+    NewFromTemplate(String)
 }
 
 #[allow(clippy::large_enum_variant)]
 pub enum Response {
     Update(InputPrompt),
     New(String),
-    Rename(String)
+    Rename(String),
+    SaveTemplate(String),
+    NewFromTemplate { template: String, title: String }
 }
 
 impl InputPrompt {
     pub fn new(config: &Config, input_action: InputAction, text: String) -> Self {
         let mut text_area = match input_action {
-            InputAction::Rename => {
+            InputAction::Rename | InputAction::SaveTemplate | InputAction::NewFromTemplate(_) => {
                 let mut text = TextArea::new(vec![text]);
                 text.move_cursor(CursorMove::End);
                 text
@@ -66,7 +71,12 @@ impl InputPrompt {
             let text = self.text_area.into_lines().remove(0);
             return match self.input_action {
                 InputAction::New => Response::New(text),
-                InputAction::Rename => Response::Rename(text)
+                InputAction::Rename => Response::Rename(text),
+                InputAction::SaveTemplate => Response::SaveTemplate(text),
+                InputAction::NewFromTemplate(template) => Response::NewFromTemplate {
+                    template,
+                    title: text
+                }
             }
         }
 
@@ -80,7 +90,9 @@ impl Prompt for InputPrompt {
     fn title(&self) -> &'static str {
         match self.input_action {
             InputAction::Rename => "Rename item",
-            InputAction::New => "New item"
+            InputAction::New => "New item",
+            InputAction::SaveTemplate => "Save project as template",
+            InputAction::NewFromTemplate(_) => "Name the new project"
         }
     }
 
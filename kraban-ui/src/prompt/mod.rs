@@ -1,9 +1,19 @@
+pub mod blockers;
+pub mod boards;
+pub mod checklist;
 pub mod delete;
 pub mod difficulty;
 pub mod due_date;
 pub mod input;
 pub mod move_to_column;
+pub mod move_to_project;
+pub mod notes;
+pub mod orphaned_columns;
 pub mod priority;
+pub mod recurrence;
+pub mod tags;
+pub mod task_detail;
+pub mod templates;
 
 use enum_dispatch::enum_dispatch;
 use kraban_config::Config;
@@ -18,12 +28,22 @@ use ratatui::{
 
 use crate::{
     prompt::{
+        blockers::BlockersPrompt,
+        boards::BoardsPrompt,
+        checklist::ChecklistPrompt,
         delete::{ProjectDeleteConfirmation, TaskDeleteConfirmation},
         difficulty::DifficultyPrompt,
         due_date::DueDatePrompt,
         input::InputPrompt,
         move_to_column::MoveToColumnPrompt,
-        priority::PriorityPrompt
+        move_to_project::MoveToProjectPrompt,
+        notes::NotesPrompt,
+        orphaned_columns::OrphanedColumnsPrompt,
+        priority::PriorityPrompt,
+        recurrence::RecurrencePrompt,
+        tags::TagsPrompt,
+        task_detail::TaskDetailPrompt,
+        templates::TemplatesPrompt
     },
     utils::block_widget
 };
@@ -72,9 +92,23 @@ pub fn render_prompt<T: Prompt>(
 pub enum ProjectsPrompt {
     InputPrompt,
     PriorityPrompt(PriorityPrompt<Project>),
+    ProjectDeleteConfirmation,
+    BoardsPrompt,
+    OrphanedColumnsPrompt,
+    TemplatesPrompt
+}
+
+#[enum_dispatch(Prompt)]
+pub enum ArchivePrompt {
     ProjectDeleteConfirmation
 }
 
+#[enum_dispatch(Prompt)]
+pub enum DueTasksPrompt {
+    TagsPrompt,
+    MoveToProjectPrompt
+}
+
 #[allow(clippy::large_enum_variant)]
 #[enum_dispatch(Prompt)]
 pub enum TasksPrompt<'a> {
@@ -83,5 +117,12 @@ pub enum TasksPrompt<'a> {
     DifficultyPrompt,
     DueDatePrompt,
     MoveToColumnPrompt(MoveToColumnPrompt<'a>),
-    TaskDeleteConfirmation(TaskDeleteConfirmation<'a>)
+    MoveToProjectPrompt,
+    TaskDeleteConfirmation(TaskDeleteConfirmation<'a>),
+    NotesPrompt,
+    ChecklistPrompt(ChecklistPrompt<'a>),
+    TagsPrompt,
+    BlockersPrompt,
+    RecurrencePrompt,
+    TaskDetailPrompt(TaskDetailPrompt<'a>)
 }
@@ -23,7 +23,7 @@ struct MoveToColumnQuery<'a> {
 }
 
 pub enum Response<'a> {
-    MoveToColumn(&'a str),
+    MoveToColumn(&'a ColumnConfig),
     Update(MoveToColumnPrompt<'a>)
 }
 
@@ -44,7 +44,7 @@ impl<'a> MoveToColumnPrompt<'a> {
             ..
         } = key
         {
-            let column = &self.0.columns(config).nth(self.0.selected()).unwrap().name;
+            let column = self.0.columns(config).nth(self.0.selected()).unwrap();
             return Response::MoveToColumn(column);
         }
 
@@ -0,0 +1,158 @@
+use kraban_config::{ColumnConfig, Config};
+use kraban_state::{Identifiable, Project, ProjectId, State};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::Widget
+};
+
+use super::Prompt;
+use crate::{
+    keyhints::Keyhints,
+    list::{List, ListQuery}
+};
+
+/// Picks a project and then one of its columns to move or copy a task to
+pub struct MoveToProjectPrompt {
+    projects: List<ProjectsQuery>,
+    /// The picked project
+    columns: Option<(ProjectId, List<ColumnsQuery>)>
+}
+
+struct ProjectsQuery {
+    current: ProjectId
+}
+
+struct ColumnsQuery;
+
+pub enum Response<'a> {
+    Transfer(Transfer<'a>),
+    Update(MoveToProjectPrompt)
+}
+
+pub struct Transfer<'a> {
+    pub project: ProjectId,
+    pub column: &'a ColumnConfig,
+    /// The task is copied instead of moved
+    pub copy: bool
+}
+
+impl ProjectsQuery {
+    /// Every project that isn't archived but the one the task is in
+    fn projects<'a>(&self, state: &'a State, config: &Config) -> impl Iterator<Item = &'a Project> {
+        state
+            .sorted_projects(config)
+            .filter(|project| !project.archived && project.id() != self.current)
+    }
+}
+
+impl MoveToProjectPrompt {
+    pub fn new(current: ProjectId) -> Self {
+        Self {
+            projects: List::new(ProjectsQuery { current }),
+            columns: None
+        }
+    }
+
+    pub fn on_key<'a>(mut self, key: KeyEvent, state: &State, config: &'a Config) -> Response<'a> {
+        const NONE: KeyModifiers = KeyModifiers::NONE;
+        let transfer = |project: ProjectId, columns: &List<ColumnsQuery>, copy: bool| {
+            let column = config.column_configs().nth(columns.selected()).unwrap();
+            Response::Transfer(Transfer {
+                project,
+                column,
+                copy
+            })
+        };
+        match (&mut self.columns, key.code, key.modifiers) {
+            (None, KeyCode::Enter, NONE) => {
+                let selected = self.projects.selected();
+                self.columns = self
+                    .projects
+                    .projects(state, config)
+                    .nth(selected)
+                    .map(|project| (project.id(), List::new(ColumnsQuery)));
+            }
+            (Some((project, columns)), KeyCode::Enter, NONE) => {
+                return transfer(*project, columns, false)
+            }
+            (Some((project, columns)), KeyCode::Char('c'), NONE) => {
+                return transfer(*project, columns, true)
+            }
+            (Some(_), KeyCode::Backspace, NONE) => self.columns = None,
+            (None, ..) => self.projects.on_key(key),
+            (Some((_, columns)), ..) => columns.on_key(key)
+        }
+
+        Response::Update(self)
+    }
+}
+
+impl ListQuery for ProjectsQuery {
+    fn get_items<'a>(
+        &self,
+        state: &'a State,
+        config: &'a Config
+    ) -> impl Iterator<Item = Line<'a>> {
+        self.projects(state, config)
+            .map(|project| Line::raw(&project.title))
+    }
+}
+
+impl ListQuery for ColumnsQuery {
+    fn get_items<'a>(&self, _: &'a State, config: &'a Config) -> impl Iterator<Item = Line<'a>> {
+        config
+            .column_configs()
+            .map(|column| Line::raw(&column.name).fg(column.color))
+    }
+}
+
+impl Prompt for MoveToProjectPrompt {
+    fn height(&self, state: &State, config: &Config) -> u16 {
+        match &self.columns {
+            None => self.projects.projects(state, config).count().max(1) as u16,
+            Some(_) => config.column_configs().count() as u16
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self.columns {
+            None => "Move or copy task to project",
+            Some(_) => "Move or copy task to column"
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
+        match &mut self.columns {
+            None if self.projects.projects(state, config).next().is_none() => {
+                Line::from("There are no other projects".italic().dark_gray())
+                    .centered()
+                    .render(area, buf)
+            }
+            None => self.projects.render(area, buf, state, config),
+            Some((_, columns)) => columns.render(area, buf, state, config)
+        }
+    }
+}
+
+impl Keyhints for MoveToProjectPrompt {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        let picked = self.columns.is_some();
+        self.projects
+            .keyhints(state, config)
+            .into_iter()
+            .chain((!picked).then_some(("Enter", "Pick project")))
+            .chain(
+                [
+                    ("Enter", "Move here"),
+                    ("c", "Copy here"),
+                    ("Backspace", "Back to projects")
+                ]
+                .into_iter()
+                .filter(move |_| picked)
+            )
+    }
+}
@@ -0,0 +1,68 @@
+use kraban_config::Config;
+use kraban_state::State;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::{Style, Stylize},
+    widgets::Widget
+};
+use tui_textarea::TextArea;
+
+use super::Prompt;
+use crate::keyhints::Keyhints;
+
+#[derive(Debug)]
+pub struct NotesPrompt {
+    text_area: TextArea<'static>
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum Response {
+    Update(NotesPrompt),
+    Save(String)
+}
+
+impl NotesPrompt {
+    pub fn new(config: &Config, description: &str) -> Self {
+        let mut text_area = TextArea::from(description.lines());
+        text_area.set_placeholder_text("Write notes about the task");
+        text_area.set_selection_style(Style::new().fg(config.app_color).reversed());
+        text_area.set_cursor_line_style(Style::new());
+        Self { text_area }
+    }
+
+    pub fn on_key(mut self, key: KeyEvent) -> Response {
+        if let KeyEvent {
+            code: KeyCode::Char('s'),
+            modifiers: KeyModifiers::CONTROL,
+            ..
+        } = key
+        {
+            let text = self.text_area.into_lines().join("\n");
+            return Response::Save(text.trim_end().to_string());
+        }
+
+        self.text_area.input(key);
+        Response::Update(self)
+    }
+}
+
+impl Prompt for NotesPrompt {
+    fn height(&self, _: &State, _: &Config) -> u16 { 12 }
+    fn width(&self) -> u16 { 80 }
+    fn title(&self) -> &'static str { "Task notes" }
+    fn render(&mut self, area: Rect, buf: &mut Buffer, _: &State, _: &Config) {
+        self.text_area.render(area, buf)
+    }
+}
+
+impl Keyhints for NotesPrompt {
+    fn keyhints(&self, _: &State, _: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        [
+            ("Ctrl-s", "Save"),
+            ("Enter", "New line"),
+            ("Other", "Consult readme")
+        ]
+    }
+}
@@ -0,0 +1,106 @@
+use kraban_config::{ColumnConfig, Config};
+use kraban_state::State;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::Widget
+};
+
+use super::Prompt;
+use crate::{
+    keyhints::Keyhints,
+    list::{List, ListQuery}
+};
+
+/// Asks what to do with the tasks of a column that was renamed or removed from the config
+pub struct OrphanedColumnsPrompt {
+    column: String,
+    list: List<ResolutionsQuery>
+}
+
+struct ResolutionsQuery;
+
+pub enum Response<'a> {
+    Update(OrphanedColumnsPrompt),
+    MoveTo(String, &'a ColumnConfig),
+    Hide(String),
+    Delete(String)
+}
+
+impl OrphanedColumnsPrompt {
+    /// None when every orphaned column was dealt with
+    pub fn new(state: &State, config: &Config) -> Option<Self> {
+        Some(Self {
+            column: state.unresolved_orphaned_column(config)?.to_string(),
+            list: List::new(ResolutionsQuery)
+        })
+    }
+
+    pub fn on_key(mut self, key: KeyEvent, config: &Config) -> Response<'_> {
+        if (key.code, key.modifiers) != (KeyCode::Enter, KeyModifiers::NONE) {
+            self.list.on_key(key);
+            return Response::Update(self);
+        }
+
+        let selected = self.list.selected();
+        let columns = config.column_configs().count();
+        match selected.checked_sub(columns) {
+            None => Response::MoveTo(self.column, config.column_configs().nth(selected).unwrap()),
+            Some(0) => Response::Hide(self.column),
+            Some(_) => Response::Delete(self.column)
+        }
+    }
+}
+
+impl ListQuery for ResolutionsQuery {
+    fn get_items<'a>(&self, _: &'a State, config: &'a Config) -> impl Iterator<Item = Line<'a>> {
+        config
+            .column_configs()
+            .map(|column| {
+                Line::from_iter([
+                    "Move tasks to ".into(),
+                    column.name.as_str().fg(column.color)
+                ])
+            })
+            .chain([
+                Line::raw("Keep them hidden"),
+                Line::raw("Delete tasks").red()
+            ])
+    }
+}
+
+impl Prompt for OrphanedColumnsPrompt {
+    fn height(&self, _: &State, config: &Config) -> u16 {
+        config.column_configs().count() as u16 + 3
+    }
+
+    fn title(&self) -> &'static str { "Column missing from the config" }
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
+        let orphans = state.orphaned_columns(config);
+        let orphans = orphans.iter().filter(|orphan| orphan.column == self.column);
+        let (tasks, projects) = orphans.fold((0, 0), |(tasks, projects), orphan| {
+            (tasks + orphan.tasks, projects + 1)
+        });
+
+        let [message_area, list_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+        Line::from_iter([
+            self.column.as_str().bold(),
+            format!(" holds {tasks} tasks in {projects} projects").into()
+        ])
+        .render(message_area, buf);
+        self.list.render(list_area, buf, state, config);
+    }
+}
+
+impl Keyhints for OrphanedColumnsPrompt {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        self.list
+            .keyhints(state, config)
+            .into_iter()
+            .chain([("Enter", "Pick")])
+    }
+}
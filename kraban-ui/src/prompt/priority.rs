@@ -10,13 +10,11 @@ use ratatui::{
     style::Stylize,
     text::Line
 };
-use strum::{EnumCount, IntoEnumIterator};
 
 use crate::{
     keyhints::Keyhints,
     list::{List, ListQuery},
-    prompt::Prompt,
-    utils::priority_to_color
+    prompt::Prompt
 };
 
 pub enum Response<T> {
@@ -43,7 +41,10 @@ impl<T: SetPriority> PriorityPrompt<T> {
         const NONE: KeyModifiers = KeyModifiers::NONE;
         match (key.code, self.currently_creating, self.list, key.modifiers) {
             (KeyCode::Enter, currently_creating, list, NONE) => {
-                let priority = Priority::iter().nth(list.selected());
+                let priority = config
+                    .priorities
+                    .get(list.selected())
+                    .map(|level| Priority::new(level.name.clone()));
                 Self::priority_selected(currently_creating, priority, config)
             }
             (KeyCode::Backspace, currenty_creating, _, NONE) => {
@@ -75,7 +76,7 @@ impl<T: SetPriority> PriorityPrompt<T> {
 }
 
 impl<T> Prompt for PriorityPrompt<T> {
-    fn height(&self, _: &State, _: &Config) -> u16 { Priority::COUNT as u16 }
+    fn height(&self, _: &State, config: &Config) -> u16 { config.priorities.len() as u16 }
     fn title(&self) -> &'static str { "Change priority" }
     fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
         self.list.render(area, buf, state, config);
@@ -95,10 +96,10 @@ impl<T> Keyhints for PriorityPrompt<T> {
 }
 
 impl ListQuery for PriorityListQuery {
-    fn get_items<'a>(&self, _: &'a State, _: &'a Config) -> impl Iterator<Item = Line<'a>> {
-        Priority::iter().map(|priority| {
-            let str: &str = priority.into();
-            Line::raw(str).fg(priority_to_color(priority))
-        })
+    fn get_items<'a>(&self, _: &'a State, config: &'a Config) -> impl Iterator<Item = Line<'a>> {
+        config
+            .priorities
+            .iter()
+            .map(|level| Line::raw(&level.name).fg(level.color))
     }
 }
@@ -0,0 +1,84 @@
+use kraban_config::Config;
+use kraban_state::{Recurrence, State};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::Widget
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use super::Prompt;
+use crate::keyhints::Keyhints;
+
+pub struct RecurrencePrompt {
+    text_area: TextArea<'static>,
+    invalid: bool
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum Response {
+    Update(RecurrencePrompt),
+    SetRecurrence(Option<Recurrence>)
+}
+
+impl RecurrencePrompt {
+    pub fn new(config: &Config, recurrence: Option<&Recurrence>) -> Self {
+        let mut text_area = TextArea::new(vec![
+            recurrence.map(Recurrence::to_string).unwrap_or_default(),
+        ]);
+        text_area.move_cursor(CursorMove::End);
+        text_area.set_placeholder_text("Doesn't repeat");
+        text_area.set_selection_style(Style::new().fg(config.app_color).reversed());
+        text_area.set_cursor_line_style(Style::new());
+        Self {
+            text_area,
+            invalid: false
+        }
+    }
+
+    pub fn on_key(mut self, key: KeyEvent) -> Response {
+        if let KeyEvent {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+            ..
+        } = key
+        {
+            let text = &self.text_area.lines()[0];
+            if text.trim().is_empty() {
+                return Response::SetRecurrence(None);
+            }
+            match Recurrence::parse(text) {
+                Some(recurrence) => return Response::SetRecurrence(Some(recurrence)),
+                None => self.invalid = true
+            }
+        } else {
+            self.text_area.input(key);
+        }
+
+        Response::Update(self)
+    }
+}
+
+impl Prompt for RecurrencePrompt {
+    fn height(&self, _: &State, _: &Config) -> u16 { 2 }
+    fn title(&self) -> &'static str { "Repeat every" }
+    fn render(&mut self, area: Rect, buf: &mut Buffer, _: &State, _: &Config) {
+        let [input_area, hint_area] = Layout::vertical([Constraint::Length(1); 2]).areas(area);
+        self.text_area.render(input_area, buf);
+        let hint = match self.invalid {
+            true => "Invalid rule, use e.g. 3d, 2w, 1m or mon,thu".red(),
+            false => "e.g. 3d, 2w, 1m or mon,thu".italic().dark_gray()
+        };
+
+        Line::from(hint).render(hint_area, buf);
+    }
+}
+
+impl Keyhints for RecurrencePrompt {
+    fn keyhints(&self, _: &State, _: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        [("Enter", "Submit"), ("Empty", "Don't repeat")]
+    }
+}
@@ -0,0 +1,144 @@
+use itertools::chain;
+use kraban_config::Config;
+use kraban_state::State;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::Widget
+};
+
+use super::Prompt;
+use crate::{
+    keyhints::Keyhints,
+    list::{List, ListQuery},
+    utils::tag_to_span
+};
+
+pub struct TagsPrompt(List<TagsQuery>);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TagsAction {
+    SetTags,
+    Filter
+}
+
+struct TagsQuery {
+    action: TagsAction,
+    selected: Vec<String>
+}
+
+pub enum Response {
+    Update(TagsPrompt),
+    Submit(TagsAction, Vec<String>)
+}
+
+impl TagsQuery {
+    /// Configured tags followed by the selected ones that are missing from the config, so they can still be removed
+    fn tags<'a>(&'a self, config: &'a Config) -> impl Iterator<Item = &'a str> {
+        let configured = config.tags.iter().map(|tag| tag.name.as_str());
+        let unknown = self
+            .selected
+            .iter()
+            .filter(|tag| config.tag_color(tag).is_none())
+            .map(String::as_str);
+        configured.chain(unknown)
+    }
+
+    fn toggle(&mut self, tag: &str) {
+        match self.selected.iter().position(|selected| selected == tag) {
+            Some(idx) => {
+                self.selected.remove(idx);
+            }
+            None => self.selected.push(tag.to_string())
+        }
+    }
+}
+
+impl TagsPrompt {
+    pub fn new(action: TagsAction, selected: Vec<String>) -> Self {
+        Self(List::new(TagsQuery { action, selected }))
+    }
+
+    pub fn on_key(mut self, key: KeyEvent, config: &Config) -> Response {
+        let tags_len = self.0.tags(config).count();
+        let selected = (tags_len != 0).then(|| self.0.selected().min(tags_len - 1));
+        match (key.code, key.modifiers, selected) {
+            (KeyCode::Char(' '), KeyModifiers::NONE, Some(idx)) => {
+                let tag = self.0.tags(config).nth(idx).unwrap().to_string();
+                self.0.toggle(&tag);
+                Response::Update(self)
+            }
+            (KeyCode::Enter, KeyModifiers::NONE, _) => {
+                let TagsQuery { action, selected } = self.0.into_query();
+                Response::Submit(action, selected)
+            }
+            _ => {
+                self.0.on_key(key);
+                Response::Update(self)
+            }
+        }
+    }
+}
+
+impl ListQuery for TagsQuery {
+    fn get_items<'a>(&self, _: &'a State, config: &'a Config) -> impl Iterator<Item = Line<'a>> {
+        let items = self.tags(config).map(|tag| {
+            let checkbox = match self.selected.iter().any(|selected| selected == tag) {
+                true => "[x] ",
+                false => "[ ] "
+            };
+
+            Line::from_iter([checkbox.fg(config.app_color), tag_to_span(tag, config)])
+        });
+
+        Vec::from_iter(items).into_iter()
+    }
+}
+
+impl Prompt for TagsPrompt {
+    fn height(&self, _: &State, config: &Config) -> u16 {
+        const MAX_TAGS: u16 = 15;
+        (self.0.tags(config).count() as u16).clamp(1, MAX_TAGS)
+    }
+
+    fn title(&self) -> &'static str {
+        match self.0.action {
+            TagsAction::SetTags => "Set tags",
+            TagsAction::Filter => "Filter by tags"
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
+        if self.0.tags(config).next().is_none() {
+            Line::from(
+                "No tags configured, add them with [[tag]] in the config"
+                    .italic()
+                    .dark_gray()
+            )
+            .render(area, buf);
+        } else {
+            self.0.render(area, buf, state, config);
+        }
+    }
+}
+
+impl Keyhints for TagsPrompt {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        let submit_hint = match self.0.action {
+            TagsAction::SetTags => ("Enter", "Save"),
+            TagsAction::Filter => ("Enter", "Apply filter")
+        };
+
+        let has_tags = self.0.tags(config).next().is_some();
+        chain![
+            has_tags
+                .then(|| chain![self.0.keyhints(state, config), [("Space", "Toggle")]])
+                .into_iter()
+                .flatten(),
+            [submit_hint]
+        ]
+    }
+}
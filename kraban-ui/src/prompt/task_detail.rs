@@ -0,0 +1,168 @@
+use std::iter;
+
+use kraban_config::Config;
+use kraban_lib::{Timestamp, format_duration};
+use kraban_state::{Activity, Change, Difficulty, Priority, ProjectId, State, Task, TaskId};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyEvent,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::Widget
+};
+use time::Date;
+
+use super::Prompt;
+use crate::{
+    keyhints::Keyhints,
+    list::{List, ListQuery}
+};
+
+/// The fields of a task and its history, newest first
+pub struct TaskDetailPrompt<'a>(List<ActivityQuery<'a>>);
+
+struct ActivityQuery<'a> {
+    project: ProjectId,
+    column: &'a str,
+    task: TaskId
+}
+
+impl ActivityQuery<'_> {
+    fn task<'a>(&self, state: &'a State) -> Option<&'a Task> {
+        state
+            .project(self.project)
+            .and_then(|project| project.columns.get(self.column).get_by_id(self.task))
+    }
+}
+
+impl<'a> TaskDetailPrompt<'a> {
+    pub fn new(project: ProjectId, column: &'a str, task: TaskId) -> Self {
+        Self(List::new(ActivityQuery {
+            project,
+            column,
+            task
+        }))
+    }
+
+    pub fn on_key(mut self, key: KeyEvent) -> Self {
+        self.0.on_key(key);
+        self
+    }
+}
+
+impl ListQuery for ActivityQuery<'_> {
+    fn get_items<'a>(
+        &self,
+        state: &'a State,
+        config: &'a Config
+    ) -> impl Iterator<Item = Line<'a>> {
+        self.task(state)
+            .into_iter()
+            .flat_map(|task| task.activity().iter().rev())
+            .map(|activity| activity_to_line(activity, config))
+    }
+}
+
+const MAX_ACTIVITY_HEIGHT: u16 = 12;
+const DETAILS_HEIGHT: u16 = 5;
+
+impl Prompt for TaskDetailPrompt<'_> {
+    fn height(&self, state: &State, _: &Config) -> u16 {
+        let activity = self.0.task(state).map_or(0, |task| task.activity().len());
+        DETAILS_HEIGHT + (activity as u16).clamp(1, MAX_ACTIVITY_HEIGHT)
+    }
+
+    fn width(&self) -> u16 { 80 }
+    fn title(&self) -> &'static str { "Task details" }
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
+        let Some(task) = self.0.task(state) else {
+            return;
+        };
+
+        let [details_area, activity_area] =
+            Layout::vertical([Constraint::Length(DETAILS_HEIGHT), Constraint::Min(0)]).areas(area);
+        let timestamp = |timestamp: Option<Timestamp>| {
+            timestamp.map_or("never".to_string(), |timestamp| {
+                timestamp.format("%Y-%m-%d %H:%M").to_string()
+            })
+        };
+
+        let details = [
+            Line::from_iter([
+                format!("#{} ", task.number()).dark_gray(),
+                task.title.as_str().bold()
+            ]),
+            Line::from(format!(
+                "Priority {}, difficulty {}, due {}",
+                task.priority().map_or("none", Priority::name),
+                task.difficulty.as_ref().map_or("none", Difficulty::name),
+                date(task.due_date())
+            )),
+            Line::from(format!(
+                "Created {}, updated {}, completed {}",
+                timestamp(Some(task.created_at())),
+                timestamp(Some(task.updated_at())),
+                timestamp(task.completed_at())
+            )),
+            Line::from(format!(
+                "Tracked {}",
+                format_duration(task.tracked_time(None))
+            )),
+            Line::from("History".bold().fg(config.app_color))
+        ];
+
+        for (line, area) in details.into_iter().zip(details_area.rows()) {
+            line.render(area, buf);
+        }
+
+        self.0.render(activity_area, buf, state, config);
+    }
+}
+
+fn date(date: Option<Date>) -> String { date.map_or("none".to_string(), |date| date.to_string()) }
+
+fn activity_to_line<'a>(activity: &'a Activity, config: &Config) -> Line<'a> {
+    let change: Vec<Span> = match &activity.change {
+        Change::EnteredColumn { column } => {
+            let color = config
+                .column_configs()
+                .find(|config| config.name == *column)
+                .map_or(Color::DarkGray, |config| config.color);
+            vec!["entered ".into(), column.as_str().fg(color)]
+        }
+        Change::Renamed { from, to } => vec![format!("renamed '{from}' to '{to}'").into()],
+        Change::Priority { from, to } => {
+            let (from, to) = (from.as_ref(), to.as_ref());
+            let (from, to) = (
+                from.map_or("none", Priority::name),
+                to.map_or("none", Priority::name)
+            );
+            vec![format!("priority {from} → {to}").into()]
+        }
+        Change::Difficulty { from, to } => {
+            let (from, to) = (from.as_ref(), to.as_ref());
+            let (from, to) = (
+                from.map_or("none", Difficulty::name),
+                to.map_or("none", Difficulty::name)
+            );
+            vec![format!("difficulty {from} → {to}").into()]
+        }
+        Change::DueDate { from, to } => {
+            vec![format!("due date {} → {}", date(*from), date(*to)).into()]
+        }
+    };
+
+    let at = activity
+        .at
+        .format("%Y-%m-%d %H:%M ")
+        .to_string()
+        .dark_gray();
+    Line::from_iter(iter::once(at).chain(change))
+}
+
+impl Keyhints for TaskDetailPrompt<'_> {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        self.0.keyhints(state, config)
+    }
+}
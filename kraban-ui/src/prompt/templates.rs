@@ -0,0 +1,92 @@
+use kraban_config::Config;
+use kraban_state::State;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::Widget
+};
+
+use super::Prompt;
+use crate::{
+    keyhints::Keyhints,
+    list::{List, ListQuery}
+};
+
+/// Picks the template to create a project from
+pub struct TemplatesPrompt(List<TemplatesQuery>);
+
+struct TemplatesQuery;
+
+pub enum Response {
+    Update(TemplatesPrompt),
+    Pick(String),
+    Delete(TemplatesPrompt, String)
+}
+
+impl TemplatesPrompt {
+    pub fn new() -> Self { Self(List::new(TemplatesQuery)) }
+    pub fn on_key(mut self, key: KeyEvent, state: &State) -> Response {
+        let selected = state
+            .templates()
+            .get(self.0.selected())
+            .map(|template| template.name.clone());
+        match (key.code, key.modifiers, selected) {
+            (KeyCode::Enter, KeyModifiers::NONE, Some(template)) => Response::Pick(template),
+            (KeyCode::Delete | KeyCode::Backspace, KeyModifiers::NONE, Some(template)) => {
+                let last = state.templates().len().saturating_sub(2);
+                self.0.select(self.0.selected().min(last));
+                Response::Delete(self, template)
+            }
+            _ => {
+                self.0.on_key(key);
+                Response::Update(self)
+            }
+        }
+    }
+}
+
+impl ListQuery for TemplatesQuery {
+    fn get_items<'a>(&self, state: &'a State, _: &'a Config) -> impl Iterator<Item = Line<'a>> {
+        state.templates().iter().map(|template| {
+            Line::from_iter([
+                template.name.as_str().into(),
+                format!(" {} tasks", template.tasks.len()).dark_gray()
+            ])
+        })
+    }
+}
+
+impl Prompt for TemplatesPrompt {
+    fn height(&self, state: &State, _: &Config) -> u16 { state.templates().len().max(1) as u16 }
+    fn title(&self) -> &'static str { "New project from template" }
+    fn render(&mut self, area: Rect, buf: &mut Buffer, state: &State, config: &Config) {
+        if state.templates().is_empty() {
+            Line::from(
+                "No templates, save a project as one first"
+                    .italic()
+                    .dark_gray()
+            )
+            .centered()
+            .render(area, buf)
+        } else {
+            self.0.render(area, buf, state, config)
+        }
+    }
+}
+
+impl Keyhints for TemplatesPrompt {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        (!state.templates().is_empty())
+            .then(|| {
+                self.0.keyhints(state, config).into_iter().chain([
+                    ("Enter", "Pick template"),
+                    ("Delete/Backspace", "Delete template")
+                ])
+            })
+            .into_iter()
+            .flatten()
+    }
+}
@@ -23,7 +23,21 @@ impl<'a> Ui<'a> {
                 buf.set_style(area, Style::new().dim());
                 render_prompt(projects_prompt, area, buf, state, config);
             }
+            UiState::DueTasksPrompt(projects, due_tasks, due_tasks_prompt) => {
+                MainViewFocus::DueTasks
+                    .render(area, buf, state, config, false, projects, due_tasks);
+                buf.set_style(area, Style::new().dim());
+                render_prompt(due_tasks_prompt, area, buf, state, config);
+            }
             UiState::TasksView(tasks_view) => tasks_view.render(area, buf, state, config, true),
+            UiState::ArchiveView(archive) => archive.render(area, buf, state, config, true),
+            UiState::TrashView(trash) => trash.render(area, buf, state, config, true),
+            UiState::FlowView(flow) => flow.render(area, buf, state, config),
+            UiState::ArchivePrompt(archive, archive_prompt) => {
+                archive.render(area, buf, state, config, false);
+                buf.set_style(area, Style::new().dim());
+                render_prompt(archive_prompt, area, buf, state, config);
+            }
             UiState::TasksPrompt(tasks_view, tasks_prompt) => {
                 tasks_view.render(area, buf, state, config, false);
                 buf.set_style(area, Style::new().dim());
@@ -37,9 +51,20 @@ impl<'a> Ui<'a> {
             iter::once(("Ctrl-q", "Quit")),
             matches!(
                 self.0,
-                UiState::ProjectsPrompt(..) | UiState::TasksPrompt(..),
+                UiState::ProjectsPrompt(..)
+                    | UiState::DueTasksPrompt(..)
+                    | UiState::TasksPrompt(..)
+                    | UiState::ArchivePrompt(..),
+            )
+            .then_some(("Esc", "Exit prompt")),
+            matches!(
+                self.0,
+                UiState::MainView(..)
+                    | UiState::TasksView(..)
+                    | UiState::ArchiveView(..)
+                    | UiState::TrashView(..)
             )
-            .then_some(("Esc", "Exit prompt"))
+            .then_some(("u/Ctrl-r", "Undo/redo"))
         ];
 
         let keyhints = config
@@ -58,13 +83,38 @@ impl<'a> Ui<'a> {
             None => area
         };
 
-        let main_block = block_widget(config)
-            .title(
-                concat!("kraban v", env!("CARGO_PKG_VERSION"))
+        let title = match &config.board {
+            board if board.is_default() => {
+                concat!("kraban v", env!("CARGO_PKG_VERSION")).to_string()
+            }
+            board => format!("kraban v{} - {board}", env!("CARGO_PKG_VERSION"))
+        };
+
+        let mut main_block = block_widget(config)
+            .title(title.fg(config.app_color).into_centered_line())
+            .borders(Borders::TOP | Borders::BOTTOM);
+        if let Some((_, task)) = state.running_timer() {
+            let elapsed = task.tracked_time(None).num_seconds();
+            let timer = format!(
+                " ⏱ {} {}:{:02}:{:02} ",
+                task.title,
+                elapsed / 3600,
+                elapsed / 60 % 60,
+                elapsed % 60
+            );
+            main_block = main_block.title(timer.fg(config.app_color).into_right_aligned_line());
+        }
+
+        if let Some(message) = &self.1 {
+            main_block = main_block.title_bottom(
+                message
+                    .as_str()
                     .fg(config.app_color)
+                    .italic()
                     .into_centered_line()
-            )
-            .borders(Borders::TOP | Borders::BOTTOM);
+            );
+        }
+
         let view_area = main_block.inner(main_area);
         main_block.render(main_area, buf);
         self.render_view(view_area, buf, state, config);
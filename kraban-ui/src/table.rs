@@ -1,4 +1,4 @@
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 
 use kraban_config::Config;
 use kraban_state::State;
@@ -21,42 +21,65 @@ macro_rules! table {(
 
 pub(crate) use table;
 
-pub struct Table<Q, const COLUMNS: usize> {
+/// A table whose selection follows the selected item by its id, so that it doesn't jump to another item when the items are re-sorted
+pub struct Table<Q: TableQuery<COLUMNS>, const COLUMNS: usize> {
     selected: TableState,
+    selected_id: Option<Q::Id>,
     query: Q
 }
 
-impl<Q: Default, const COLUMNS: usize> Default for Table<Q, COLUMNS> {
-    fn default() -> Self {
-        Self {
-            selected: TableState::default(),
-            query: Q::default()
-        }
-    }
+impl<Q: TableQuery<COLUMNS> + Default, const COLUMNS: usize> Default for Table<Q, COLUMNS> {
+    fn default() -> Self { Self::new(None, Q::default()) }
 }
 
-impl<Q, const COLUMNS: usize> Deref for Table<Q, COLUMNS> {
+impl<Q: TableQuery<COLUMNS>, const COLUMNS: usize> Deref for Table<Q, COLUMNS> {
     type Target = Q;
 
     fn deref(&self) -> &Self::Target { &self.query }
 }
 
+impl<Q: TableQuery<COLUMNS>, const COLUMNS: usize> DerefMut for Table<Q, COLUMNS> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.query }
+}
+
 impl<Q: TableQuery<COLUMNS>, const COLUMNS: usize> Table<Q, COLUMNS> {
-    pub fn new(idx: usize, query: Q) -> Self {
-        let selected = TableState::new().with_selected(idx);
-        Self { selected, query }
+    pub fn new(selected_id: Option<Q::Id>, query: Q) -> Self {
+        Self {
+            selected: TableState::new().with_selected(0),
+            selected_id,
+            query
+        }
     }
 
+    /// If the selected item no longer exists, the item at its former position is selected
     pub fn selected(&self, state: &State, config: &Config) -> Option<usize> {
+        self.selected_id
+            .and_then(|id| self.query.ids(state, config).position(|other| other == id))
+            .or_else(|| {
+                self.selected
+                    .selected()
+                    .min(self.query.len(state, config).checked_sub(1))
+            })
+    }
+
+    pub fn selected_id(&self, state: &State, config: &Config) -> Option<Q::Id> {
+        let idx = self.selected(state, config)?;
+        self.query.ids(state, config).nth(idx)
+    }
+
+    fn sync_selection(&mut self, state: &State, config: &Config) {
         self.selected
-            .selected()
-            .min(self.query.len(state, config).checked_sub(1))
+            .select(Some(self.selected(state, config).unwrap_or_default()));
+        self.selected_id = self.selected_id(state, config);
     }
 }
 
 pub trait TableQuery<const COLUMNS: usize> {
     const COLUMNS: usize = COLUMNS;
     const CONSTRAINTS: [Constraint; COLUMNS];
+    type Id: Copy + PartialEq;
+    /// Ids of the items in the order of the rows
+    fn ids<'a>(&self, state: &'a State, config: &'a Config) -> impl Iterator<Item = Self::Id>;
     fn rows<'a>(
         &self,
         state: &'a State,
@@ -84,8 +107,11 @@ where
             KeyCode::Down => self.selected.select_next(),
             KeyCode::Home => self.selected.select_first(),
             KeyCode::End => self.selected.select_last(),
-            _ => {}
+            _ => return
         }
+
+        // The id gets updated from the new position when rendering
+        self.selected_id = None;
     }
 
     pub fn render(
@@ -103,9 +129,7 @@ where
             .row_highlight_style(Style::new().on_black())
             .highlight_symbol(">");
 
-        if self.selected.selected().is_none() {
-            self.selected.select(Some(0));
-        }
+        self.sync_selection(state, config);
 
         if focused {
             StatefulWidget::render(table, area, buf, &mut self.selected)
@@ -115,7 +139,7 @@ where
     }
 }
 
-impl<const COLUMNS: usize, Q> Keyhints for Table<Q, COLUMNS> {
+impl<const COLUMNS: usize, Q: TableQuery<COLUMNS>> Keyhints for Table<Q, COLUMNS> {
     fn keyhints(&self, _: &State, _: &Config) -> impl IntoIterator<Item = (&str, &str)> {
         [
             ("Up/Down", "Select previous/next"),
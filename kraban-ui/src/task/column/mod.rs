@@ -1,59 +1,65 @@
 mod query;
 mod render;
 
-use std::iter;
-
 use itertools::chain;
 use kraban_config::{ColumnConfig, Config};
-use kraban_state::{Column, Project, State, Task};
+use kraban_state::{Identifiable, Project, ProjectId, State, Task, TaskId};
 use query::TaskTable;
-use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
-    style::Color
-};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::{
     TasksPrompt,
     keyhints::Keyhints,
     prompt::{
+        blockers::BlockersPrompt,
+        checklist::ChecklistPrompt,
         delete::TaskDeleteConfirmation,
         difficulty::DifficultyPrompt,
         due_date::DueDatePrompt,
         input::{InputAction, InputPrompt},
         move_to_column::MoveToColumnPrompt,
-        priority::PriorityPrompt
+        move_to_project::MoveToProjectPrompt,
+        notes::NotesPrompt,
+        priority::PriorityPrompt,
+        recurrence::RecurrencePrompt,
+        tags::{TagsAction, TagsPrompt},
+        task_detail::TaskDetailPrompt
     },
     table::{Table, TableQuery, table}
 };
 
+#[derive(Clone, Copy, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Direction {
+    Up,
+    Down
+}
+
 pub struct ColumnView<'a> {
-    color: Color,
-    column: &'a str,
-    project_idx: usize,
+    column: &'a ColumnConfig,
+    project: ProjectId,
     table: table!(TaskTable<'a>)
 }
 
 impl<'a> ColumnView<'a> {
-    pub fn new(project_idx: usize, column: &'a ColumnConfig, task: usize) -> Self {
-        let color = column.color;
-        let column = &column.name;
-        let table = Table::new(task, TaskTable::new(project_idx, column));
-
+    pub fn new(project: ProjectId, column: &'a ColumnConfig, task: Option<TaskId>) -> Self {
+        let table = Table::new(task, TaskTable::new(project, column));
         Self {
-            color,
             table,
-            project_idx,
+            project,
             column
         }
     }
 
-    fn modify_selected_column<T>(&self, state: &mut State, f: impl FnOnce(&mut Column) -> T) -> T {
-        let f = |project: &mut Project| f(project.columns.get_mut(self.column));
-        state.projects_mut().modify_item_at(self.project_idx, f)
+    pub fn column(&self) -> &'a ColumnConfig { self.column }
+    pub fn selected_task(&self, state: &State, config: &Config) -> Option<(&'a str, TaskId)> {
+        Some((&self.column.name, self.table.selected_id(state, config)?))
     }
 
     pub fn push_task(&self, task: Task, state: &mut State) {
-        self.modify_selected_column(state, |column| column.push(task));
+        state.describe_change(format!("created task '{}'", task.title));
+        let f = |project: &mut Project| project.add_task(&self.column.name, task);
+        state.projects_mut().modify_item_with_id(self.project, f);
     }
 
     pub fn modify_selected_task<T>(
@@ -62,14 +68,72 @@ impl<'a> ColumnView<'a> {
         config: &Config,
         f: impl FnOnce(&mut Task) -> T
     ) -> Option<T> {
-        let idx = self.table.selected(state, config)?;
-        Some(self.modify_selected_column(state, |column| column.modify_item_at(idx, f)))
+        let id = self.table.selected_id(state, config)?;
+        let f = |project: &mut Project| project.modify_task(&self.column.name, id, f);
+        state.projects_mut().modify_item_with_id(self.project, f)?
     }
 
-    pub fn delete_selected_task(&self, state: &mut State, config: &Config) -> Option<Task> {
-        let idx = self.table.selected(state, config)?;
-        Some(self.modify_selected_column(state, |column| column.remove(idx)))
+    /// Returns the description of the move
+    pub fn move_selected_task(
+        &self,
+        state: &mut State,
+        config: &Config,
+        to: &ColumnConfig
+    ) -> Option<String> {
+        let id = self.table.selected_id(state, config)?;
+        let f = |project: &mut Project| {
+            let task = project.move_task(id, &self.column.name, to, config)?;
+            Some(format!(
+                "moved '{}' {}→{}",
+                task.title, self.column.name, to.name
+            ))
+        };
+        state.projects_mut().modify_item_with_id(self.project, f)?
     }
+
+    /// Returns the description of the change
+    pub fn toggle_selected_task_timer(&self, state: &mut State, config: &Config) -> Option<String> {
+        let id = self.table.selected_id(state, config)?;
+        state.toggle_timer(self.project, &self.column.name, id)
+    }
+
+    /// Swaps the selected task with the one shown above or below it and returns the description of the change
+    pub fn shift_selected_task(
+        &self,
+        state: &mut State,
+        config: &Config,
+        direction: Direction
+    ) -> Option<String> {
+        if !self.table.is_manually_sorted() {
+            return None;
+        }
+
+        let id = self.table.selected_id(state, config)?;
+        let ids = Vec::from_iter(self.table.ids(state, config));
+        let idx = ids.iter().position(|other| *other == id)?;
+        let other = match direction {
+            Direction::Up => ids.get(idx.checked_sub(1)?)?,
+            Direction::Down => ids.get(idx + 1)?
+        };
+
+        let f = |project: &mut Project| {
+            project.swap_tasks(&self.column.name, id, *other)?;
+            let title = &project.columns.get(&self.column.name).get_by_id(id)?.title;
+            Some(format!("moved '{title}' {direction}"))
+        };
+        state.projects_mut().modify_item_with_id(self.project, f)?
+    }
+
+    pub fn delete_selected_task<'s>(
+        &self,
+        state: &'s mut State,
+        config: &Config
+    ) -> Option<&'s Task> {
+        let id = self.table.selected_id(state, config)?;
+        state.trash_task(self.project, &self.column.name, id)
+    }
+
+    pub fn set_tag_filter(&mut self, tags: Vec<String>) { self.table.set_tag_filter(tags) }
 }
 
 impl<'a> ColumnView<'a> {
@@ -89,21 +153,23 @@ impl<'a> ColumnView<'a> {
             return None;
         };
 
-        let task_idx = self.table.selected(state, config);
-        let current_task =
-            task_idx.map(|idx| &state.projects()[self.project_idx].columns.get(self.column)[idx]);
+        let current_task = self.table.selected_id(state, config).and_then(|id| {
+            state
+                .project(self.project)?
+                .columns
+                .get(&self.column.name)
+                .get_by_id(id)
+        });
 
         match (code, current_task) {
             (KeyCode::Char('n'), _) => {
                 let placeholder = "Enter new task name".to_owned();
                 prompt(InputPrompt::new(config, InputAction::New, placeholder))
             }
-            (KeyCode::Enter, Some(_)) => prompt(MoveToColumnPrompt::new(self.column)),
-            (KeyCode::Delete | KeyCode::Backspace, Some(_)) => prompt(TaskDeleteConfirmation::new(
-                self.project_idx,
-                self.column,
-                task_idx?
-            )),
+            (KeyCode::Enter, Some(_)) => prompt(MoveToColumnPrompt::new(&self.column.name)),
+            (KeyCode::Delete | KeyCode::Backspace, Some(current_task)) => prompt(
+                TaskDeleteConfirmation::new(self.project, &self.column.name, current_task.id())
+            ),
             (KeyCode::Char('p'), Some(_)) => prompt(PriorityPrompt::new(None)),
             (KeyCode::Char('d'), Some(_)) => prompt(DifficultyPrompt::new(None)),
             (KeyCode::Char('r'), Some(current_task)) => prompt(InputPrompt::new(
@@ -114,6 +180,37 @@ impl<'a> ColumnView<'a> {
             (KeyCode::Char('a'), Some(current_task)) => {
                 prompt(DueDatePrompt::new(None, current_task.due_date()))
             }
+            (KeyCode::Char('e'), Some(current_task)) => {
+                prompt(NotesPrompt::new(config, &current_task.description))
+            }
+            (KeyCode::Char('c'), Some(current_task)) => prompt(ChecklistPrompt::new(
+                self.project,
+                &self.column.name,
+                current_task.id()
+            )),
+            (KeyCode::Char('t'), Some(current_task)) => prompt(TagsPrompt::new(
+                TagsAction::SetTags,
+                current_task.tags.clone()
+            )),
+            (KeyCode::Char('b'), Some(current_task)) => prompt(BlockersPrompt::new(current_task)),
+            (KeyCode::Char('i'), Some(current_task)) => prompt(RecurrencePrompt::new(
+                config,
+                current_task.recurrence.as_ref()
+            )),
+            (KeyCode::Char('v'), Some(current_task)) => prompt(TaskDetailPrompt::new(
+                self.project,
+                &self.column.name,
+                current_task.id()
+            )),
+            (KeyCode::Char('m'), Some(_)) => prompt(MoveToProjectPrompt::new(self.project)),
+            (KeyCode::Char('f'), _) => prompt(TagsPrompt::new(
+                TagsAction::Filter,
+                self.table.tag_filter().to_vec()
+            )),
+            (KeyCode::Char('s'), _) => {
+                self.table.cycle_sort();
+                None
+            }
             _ => {
                 self.table.on_key(key);
                 None
@@ -128,24 +225,37 @@ fn prompt<'a, T: Into<TasksPrompt<'a>>>(prompt: T) -> Option<TasksPrompt<'a>> {
 
 impl Keyhints for ColumnView<'_> {
     fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
-        const TASK_REQUIRING_KEYHINTS: [(&str, &str); 6] = [
+        const TASK_REQUIRING_KEYHINTS: [(&str, &str); 16] = [
             ("Delete/Backspace", "Delete"),
             ("p", "Set priority"),
             ("d", "Set difficulty"),
             ("r", "Rename"),
             ("a", "Add due date"),
-            ("Enter", "Move task to column")
+            ("e", "View/edit notes"),
+            ("c", "Checklist"),
+            ("t", "Set tags"),
+            ("b", "Set blockers"),
+            ("i", "Set recurrence"),
+            ("w", "Start/stop timer"),
+            ("v", "View details and history"),
+            ("Enter", "Move task to column"),
+            ("m", "Move/copy task to project"),
+            ("y", "Duplicate task"),
+            ("s", "Sort by timestamp")
         ];
 
+        let has_tasks = self.table.len(state, config) != 0;
         chain![
-            iter::once(("n", "New")),
-            (self.table.len(state, config) != 0)
+            [("n", "New"), ("f", "Filter by tags")],
+            has_tasks
                 .then_some(chain![
                     TASK_REQUIRING_KEYHINTS,
                     self.table.keyhints(state, config)
                 ])
                 .into_iter()
-                .flatten()
+                .flatten(),
+            (has_tasks && self.table.is_manually_sorted())
+                .then_some(("Shift-Up/Down", "Move task up/down"))
         ]
     }
 }
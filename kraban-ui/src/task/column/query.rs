@@ -1,64 +1,151 @@
-use kraban_config::Config;
-use kraban_lib::now;
-use kraban_state::{State, Task};
+use std::cmp::Reverse;
+
+use kraban_config::{ColumnConfig, ColumnSort, Config};
+use kraban_lib::{Timestamp, now};
+use kraban_state::{Identifiable, ProjectId, State, Task, TaskId, sort_tasks};
 use ratatui::{layout::Constraint, text::Line};
 use time::Date;
 
 use crate::{
     table::TableQuery,
     utils::{
-        DIFFICULTY_CONSTRAINT, DUE_DATE_CONSTRAINT, PRIORITY_CONSTRAINT, difficulty_to_line,
-        due_date_to_line, priority_to_line
+        DIFFICULTY_CONSTRAINT, DUE_DATE_CONSTRAINT, PRIORITY_CONSTRAINT, TASK_NUMBER_CONSTRAINT,
+        TIMESTAMP_CONSTRAINT, difficulty_to_line, due_date_to_line, priority_to_line,
+        task_number_to_line, timestamp_to_line, title_to_line, tracked_time_to_line
     }
 };
 
 #[derive(Debug, Clone)]
 pub struct TaskTable<'a> {
-    project_idx: usize,
-    column: &'a str
+    project: ProjectId,
+    column: &'a str,
+    column_sort: ColumnSort,
+    sort: TimestampSort,
+    /// Only tasks with all of these tags are shown
+    tag_filter: Vec<String>
+}
+
+/// Which timestamp is shown in the table. Unless it's [`TimestampSort::None`], the tasks are also sorted by it, newest first,
+/// instead of by the sort of the column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum TimestampSort {
+    #[default]
+    None,
+    Created,
+    Updated,
+    Completed
+}
+
+impl TimestampSort {
+    const fn next(self) -> Self {
+        match self {
+            TimestampSort::None => TimestampSort::Created,
+            TimestampSort::Created => TimestampSort::Updated,
+            TimestampSort::Updated => TimestampSort::Completed,
+            TimestampSort::Completed => TimestampSort::None
+        }
+    }
+
+    fn timestamp(self, task: &Task) -> Option<Timestamp> {
+        match self {
+            TimestampSort::None | TimestampSort::Created => Some(task.created_at()),
+            TimestampSort::Updated => Some(task.updated_at()),
+            TimestampSort::Completed => task.completed_at()
+        }
+    }
 }
 
 impl<'a> TaskTable<'a> {
-    pub fn new(project_idx: usize, column: &'a str) -> Self {
+    pub fn new(project: ProjectId, column: &'a ColumnConfig) -> Self {
         Self {
-            project_idx,
-            column
+            project,
+            column: &column.name,
+            column_sort: column.sort,
+            sort: TimestampSort::None,
+            tag_filter: Vec::new()
+        }
+    }
+
+    pub fn sort(&self) -> TimestampSort { self.sort }
+    /// Tasks can only be reordered by hand when they are shown in their manual order
+    pub fn is_manually_sorted(&self) -> bool {
+        self.column_sort == ColumnSort::Manual && self.sort == TimestampSort::None
+    }
+
+    pub fn cycle_sort(&mut self) { self.sort = self.sort.next() }
+    pub fn tag_filter(&self) -> &[String] { &self.tag_filter }
+    pub fn set_tag_filter(&mut self, tags: Vec<String>) { self.tag_filter = tags }
+
+    /// The project might not exist anymore after an undo or a reload
+    fn tasks<'b>(&self, state: &'b State, config: &Config) -> impl Iterator<Item = &'b Task> {
+        let mut tasks = Vec::from_iter(
+            state
+                .project(self.project)
+                .into_iter()
+                .flat_map(|project| project.columns.get(self.column).iter())
+                .filter(|task| task.has_tags(&self.tag_filter))
+        );
+
+        match self.sort {
+            TimestampSort::None => sort_tasks(&mut tasks, self.column_sort, config),
+            sort => tasks.sort_by_key(|task| Reverse(sort.timestamp(task)))
         }
+
+        tasks.into_iter()
     }
 }
 
-impl TableQuery<4> for TaskTable<'_> {
-    fn len(&self, state: &State, _: &Config) -> usize {
-        state.projects()[self.project_idx]
-            .columns
-            .get(self.column)
-            .len()
+impl TableQuery<6> for TaskTable<'_> {
+    type Id = TaskId;
+    fn ids<'a>(&self, state: &'a State, config: &'a Config) -> impl Iterator<Item = TaskId> {
+        self.tasks(state, config).map(Task::id)
     }
 
-    const CONSTRAINTS: [Constraint; 4] = [
+    fn len(&self, state: &State, config: &Config) -> usize { self.tasks(state, config).count() }
+
+    const CONSTRAINTS: [Constraint; 6] = [
+        TASK_NUMBER_CONSTRAINT,
         PRIORITY_CONSTRAINT,
         DIFFICULTY_CONSTRAINT,
         DUE_DATE_CONSTRAINT,
+        TIMESTAMP_CONSTRAINT,
         Constraint::Min(0)
     ];
 
-    fn rows<'a>(&self, state: &'a State, _: &'a Config) -> impl Iterator<Item = [Line<'a>; 4]> {
+    fn rows<'a>(
+        &self,
+        state: &'a State,
+        config: &'a Config
+    ) -> impl Iterator<Item = [Line<'a>; 6]> {
         let now = now();
-        state.projects()[self.project_idx]
-            .columns
-            .get(self.column)
-            .iter()
-            .map(move |task| task_row(now, task))
+        self.tasks(state, config)
+            .map(move |task| task_row(now, task, self.sort, state, config))
     }
 }
 
-fn task_row(now: Date, task: &Task) -> [Line<'_>; 4] {
+fn task_row<'a>(
+    now: Date,
+    task: &'a Task,
+    sort: TimestampSort,
+    state: &State,
+    config: &Config
+) -> [Line<'a>; 6] {
     [
-        task.priority().map(priority_to_line).unwrap_or_default(),
-        task.difficulty.map(difficulty_to_line).unwrap_or_default(),
+        task_number_to_line(task.number()),
+        task.priority()
+            .map(|priority| priority_to_line(priority, config))
+            .unwrap_or_default(),
+        task.difficulty
+            .as_ref()
+            .map(|difficulty| difficulty_to_line(difficulty, config))
+            .unwrap_or_default(),
         task.due_date()
             .map(|date| due_date_to_line(date, now))
             .unwrap_or_default(),
-        task.title.as_str().into()
+        sort.timestamp(task)
+            .map(timestamp_to_line)
+            .unwrap_or_default(),
+        tracked_time_to_line(title_to_line(task, state, config), task, config)
     ]
 }
@@ -3,11 +3,12 @@ use kraban_state::State;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Style, Stylize},
+    style::{Color, Style, Stylize},
     text::Line,
     widgets::Widget
 };
 
+use super::query::TimestampSort;
 use crate::{table::TableQuery, task::column::ColumnView};
 
 impl<'a> ColumnView<'a> {
@@ -20,10 +21,29 @@ impl<'a> ColumnView<'a> {
         focused: bool
     ) {
         let column_len = self.table.len(state, config);
-        let len_msg = (area.height <= column_len as u16)
-            .then_some(format!(" ({column_len})"))
-            .unwrap_or_default();
-        let block_name = format!("{}{len_msg}", self.column);
+        let project = state.project(self.project);
+        let over_wip_limit = project.is_some_and(|project| project.is_over_wip_limit(self.column));
+        let len_msg = match (self.column.wip_limit, project) {
+            (Some(limit), Some(project)) => {
+                let len = project.columns.get(&self.column.name).len();
+                let warning = if over_wip_limit { " ⚠" } else { "" };
+                format!(" ({len}/{limit}){warning}")
+            }
+            _ => (area.height <= column_len as u16)
+                .then_some(format!(" ({column_len})"))
+                .unwrap_or_default()
+        };
+        let sort_msg = match self.table.sort() {
+            TimestampSort::None => String::new(),
+            sort => format!(" by {sort}")
+        };
+
+        let filter_msg = match self.table.tag_filter() {
+            [] => String::new(),
+            tags => format!(" [{}]", tags.join(", "))
+        };
+
+        let block_name = format!("{}{len_msg}{sort_msg}{filter_msg}", self.column.name);
 
         Line::from(block_name).centered().render(area, buf);
         let style = if focused {
@@ -32,7 +52,10 @@ impl<'a> ColumnView<'a> {
             Style::new().on_black()
         }
         .bold()
-        .fg(self.color);
+        .fg(match over_wip_limit {
+            true => Color::Red,
+            false => self.column.color
+        });
 
         buf.set_style(Rect { height: 1, ..area }, style);
         let area = Rect {
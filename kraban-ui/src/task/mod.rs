@@ -1,9 +1,10 @@
 mod column;
 mod tab;
 
-use kraban_config::Config;
+pub use column::Direction;
+use kraban_config::{ColumnConfig, Config};
 use kraban_lib::WrappingUsize;
-use kraban_state::{Project, State, Task};
+use kraban_state::{ProjectId, State, Task, TaskId};
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
@@ -14,7 +15,7 @@ use tab::TabView;
 use crate::{TasksPrompt, keyhints::Keyhints, projects::ProjectsView};
 
 pub struct TasksView<'a> {
-    project_idx: usize,
+    project: ProjectId,
     tabs: Vec<TabView<'a>>,
     focused_tab: WrappingUsize
 }
@@ -23,21 +24,24 @@ pub struct TasksView<'a> {
 pub enum Response<'a> {
     OpenPrompt(TasksView<'a>, TasksPrompt<'a>),
     SwitchToProjectsView(ProjectsView),
+    ShiftSelectedTask(TasksView<'a>, Direction),
+    ToggleTimer(TasksView<'a>),
+    DuplicateSelectedTask(TasksView<'a>),
     Update(TasksView<'a>)
 }
 
 impl<'a> TasksView<'a> {
-    pub fn new(project_idx: usize, config: &'a Config) -> Self {
+    pub fn new(project: ProjectId, config: &'a Config) -> Self {
         let tab_configs = &config.tabs;
         let focused_tab = WrappingUsize::new(tab_configs.len() - 1);
         let tabs = tab_configs
             .iter()
             .enumerate()
-            .map(|(idx, tab)| TabView::new(project_idx, idx, tab))
+            .map(|(idx, tab)| TabView::new(project, idx, tab))
             .collect();
 
         Self {
-            project_idx,
+            project,
             focused_tab,
             tabs
         }
@@ -47,10 +51,18 @@ impl<'a> TasksView<'a> {
         const NONE: KeyModifiers = KeyModifiers::NONE;
         match (key.code, key.modifiers) {
             (KeyCode::Esc, NONE) => {
-                return Response::SwitchToProjectsView(ProjectsView::new(self.project_idx))
+                return Response::SwitchToProjectsView(ProjectsView::new(self.project))
             }
             (KeyCode::Tab, NONE) => self.focused_tab.increment(),
             (KeyCode::BackTab, NONE) => self.focused_tab.decrement(),
+            (KeyCode::Char('w'), NONE) => return Response::ToggleTimer(self),
+            (KeyCode::Char('y'), NONE) => return Response::DuplicateSelectedTask(self),
+            (KeyCode::Up, KeyModifiers::SHIFT) => {
+                return Response::ShiftSelectedTask(self, Direction::Up)
+            }
+            (KeyCode::Down, KeyModifiers::SHIFT) => {
+                return Response::ShiftSelectedTask(self, Direction::Down)
+            }
             _ => {
                 if let Some(prompt) = self.tabs[*self.focused_tab].on_key(key, state, config) {
                     return Response::OpenPrompt(self, prompt)
@@ -85,9 +97,9 @@ impl<'a> TasksView<'a> {
     }
 
     pub fn with_specific_task(
-        project: usize,
+        project: ProjectId,
         column_name: &str,
-        task: usize,
+        task: TaskId,
         config: &'a Config
     ) -> Self {
         let tabs = &config.tabs;
@@ -100,16 +112,36 @@ impl<'a> TasksView<'a> {
         let tabs: Vec<TabView> = tabs
             .iter()
             .enumerate()
-            .map(|(idx, tab)| TabView::with_column_and_task(project, idx, tab, column, task))
+            .map(|(idx, tab)| match idx == focused_tab {
+                true => TabView::with_column_and_task(project, idx, tab, column, Some(task)),
+                false => TabView::new(project, idx, tab)
+            })
             .collect();
 
         Self {
-            project_idx: project,
+            project,
             focused_tab: WrappingUsize::new_with_value(tabs.len() - 1, focused_tab),
             tabs
         }
     }
 
+    pub fn project(&self) -> ProjectId { self.project }
+    /// The filter is shared by every column of the project
+    pub fn set_tag_filter(&mut self, tags: Vec<String>) {
+        for tab in &mut self.tabs {
+            tab.set_tag_filter(&tags)
+        }
+    }
+
+    /// The column new tasks are added to
+    pub fn focused_column(&self) -> &'a ColumnConfig {
+        self.tabs[*self.focused_tab].focused_column()
+    }
+    /// The column and id of the task selected in the focused column
+    pub fn selected_task(&self, state: &State, config: &Config) -> Option<(&'a str, TaskId)> {
+        self.tabs[*self.focused_tab].selected_task(state, config)
+    }
+
     pub fn push_task(&self, task: Task, state: &mut State) {
         self.tabs[*self.focused_tab].push_task(task, state)
     }
@@ -123,16 +155,35 @@ impl<'a> TasksView<'a> {
         self.tabs[*self.focused_tab].modify_selected_task(state, config, f)
     }
 
-    pub fn delete_selected_task(&self, state: &mut State, config: &Config) -> Option<Task> {
+    pub fn toggle_selected_task_timer(&self, state: &mut State, config: &Config) -> Option<String> {
+        self.tabs[*self.focused_tab].toggle_selected_task_timer(state, config)
+    }
+
+    pub fn shift_selected_task(
+        &self,
+        state: &mut State,
+        config: &Config,
+        direction: Direction
+    ) -> Option<String> {
+        self.tabs[*self.focused_tab].shift_selected_task(state, config, direction)
+    }
+
+    pub fn delete_selected_task<'s>(
+        &self,
+        state: &'s mut State,
+        config: &Config
+    ) -> Option<&'s Task> {
         self.tabs[*self.focused_tab].delete_selected_task(state, config)
     }
 
-    pub fn modify_selected_project<T>(
+    /// Returns the description of the move
+    pub fn move_selected_task(
         &self,
         state: &mut State,
-        f: impl FnOnce(&mut Project) -> T
-    ) -> T {
-        state.projects_mut().modify_item_at(self.project_idx, f)
+        config: &Config,
+        to: &ColumnConfig
+    ) -> Option<String> {
+        self.tabs[*self.focused_tab].move_selected_task(state, config, to)
     }
 }
 
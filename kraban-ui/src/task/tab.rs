@@ -1,6 +1,6 @@
-use kraban_config::{Config, TabConfig};
+use kraban_config::{ColumnConfig, Config, TabConfig};
 use kraban_lib::WrappingUsize;
-use kraban_state::{State, Task};
+use kraban_state::{ProjectId, State, Task, TaskId};
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
@@ -8,7 +8,7 @@ use ratatui::{
     symbols::line
 };
 
-use super::column::ColumnView;
+use super::column::{ColumnView, Direction};
 use crate::{TasksPrompt, keyhints::Keyhints};
 
 #[derive(Default)]
@@ -19,8 +19,8 @@ pub struct TabView<'a> {
 }
 
 impl<'a> TabView<'a> {
-    pub fn new(project: usize, tab_idx: usize, tab: &'a TabConfig) -> Self {
-        Self::with_column_and_task(project, tab_idx, tab, 0, 0)
+    pub fn new(project: ProjectId, tab_idx: usize, tab: &'a TabConfig) -> Self {
+        Self::with_column_and_task(project, tab_idx, tab, 0, None)
     }
 
     pub fn on_key(
@@ -69,11 +69,11 @@ impl<'a> TabView<'a> {
     }
 
     pub fn with_column_and_task(
-        project: usize,
+        project: ProjectId,
         tab_idx: usize,
         tab: &'a TabConfig,
         column_idx: usize,
-        task: usize
+        task: Option<TaskId>
     ) -> Self {
         Self {
             tab_idx,
@@ -85,6 +85,11 @@ impl<'a> TabView<'a> {
         }
     }
 
+    pub fn focused_column(&self) -> &'a ColumnConfig { self.columns[*self.focused_column].column() }
+    pub fn selected_task(&self, state: &State, config: &Config) -> Option<(&'a str, TaskId)> {
+        self.columns[*self.focused_column].selected_task(state, config)
+    }
+
     pub fn push_task(&self, task: Task, state: &mut State) {
         self.columns[*self.focused_column].push_task(task, state)
     }
@@ -98,9 +103,41 @@ impl<'a> TabView<'a> {
         self.columns[*self.focused_column].modify_selected_task(state, config, f)
     }
 
-    pub fn delete_selected_task(&self, state: &mut State, config: &Config) -> Option<Task> {
+    pub fn toggle_selected_task_timer(&self, state: &mut State, config: &Config) -> Option<String> {
+        self.columns[*self.focused_column].toggle_selected_task_timer(state, config)
+    }
+
+    pub fn shift_selected_task(
+        &self,
+        state: &mut State,
+        config: &Config,
+        direction: Direction
+    ) -> Option<String> {
+        self.columns[*self.focused_column].shift_selected_task(state, config, direction)
+    }
+
+    pub fn delete_selected_task<'s>(
+        &self,
+        state: &'s mut State,
+        config: &Config
+    ) -> Option<&'s Task> {
         self.columns[*self.focused_column].delete_selected_task(state, config)
     }
+
+    pub fn move_selected_task(
+        &self,
+        state: &mut State,
+        config: &Config,
+        to: &ColumnConfig
+    ) -> Option<String> {
+        self.columns[*self.focused_column].move_selected_task(state, config, to)
+    }
+
+    pub fn set_tag_filter(&mut self, tags: &[String]) {
+        for column in &mut self.columns {
+            column.set_tag_filter(tags.to_vec())
+        }
+    }
 }
 
 fn render_separator(area: Rect, buf: &mut Buffer, config: &Config) {
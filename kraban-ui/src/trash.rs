@@ -0,0 +1,148 @@
+use kraban_config::Config;
+use kraban_state::{Identifiable, State, TrashId, TrashItem, Trashed};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::Line,
+    widgets::Widget
+};
+
+use crate::{
+    keyhints::Keyhints,
+    table::{Table, TableQuery, table},
+    utils::{TIMESTAMP_CONSTRAINT, timestamp_to_line}
+};
+
+pub enum Response {
+    SwitchToProjectsView,
+    RestoreSelected(TrashView),
+    PurgeSelected(TrashView),
+    Update(TrashView)
+}
+
+/// Lists deleted projects and tasks, which can be restored to where they were or purged
+#[derive(Default)]
+pub struct TrashView(table!(TrashQuery));
+impl TrashView {
+    pub fn selected_id(&self, state: &State, config: &Config) -> Option<TrashId> {
+        self.0.selected_id(state, config)
+    }
+
+    pub fn render(
+        &mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &State,
+        config: &Config,
+        focused: bool
+    ) {
+        let title = match config.trash_days {
+            0 => "Trash".to_string(),
+            days => format!("Trash (items are purged after {days} days)")
+        };
+
+        Line::from(title.bold().fg(config.app_color))
+            .centered()
+            .render(area, buf);
+        let area = Rect {
+            y: area.y + 1,
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+
+        if self.0.len(state, config) == 0 {
+            Line::from("The trash is empty".italic().dark_gray())
+                .centered()
+                .render(area, buf);
+        } else {
+            self.0.render(area, buf, state, config, focused)
+        }
+    }
+
+    pub fn on_key(mut self, key: KeyEvent, state: &State, config: &Config) -> Response {
+        const NONE: KeyModifiers = KeyModifiers::NONE;
+        let selected = self.0.selected_id(state, config);
+        match (key.code, key.modifiers, selected) {
+            (KeyCode::Esc, NONE, _) => Response::SwitchToProjectsView,
+            (KeyCode::Enter, NONE, Some(_)) => Response::RestoreSelected(self),
+            (KeyCode::Delete | KeyCode::Backspace, NONE, Some(_)) => Response::PurgeSelected(self),
+            _ => {
+                self.0.on_key(key);
+                Response::Update(self)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct TrashQuery;
+impl TableQuery<3> for TrashQuery {
+    type Id = TrashId;
+    fn ids<'a>(&self, state: &'a State, _: &'a Config) -> impl Iterator<Item = TrashId> {
+        state.trash().iter().map(TrashItem::id)
+    }
+
+    fn rows<'a>(
+        &self,
+        state: &'a State,
+        config: &'a Config
+    ) -> impl Iterator<Item = [Line<'a>; 3]> {
+        state.trash().iter().map(move |item| {
+            [
+                timestamp_to_line(item.deleted_at),
+                origin_to_line(item, config),
+                item.title().into()
+            ]
+        })
+    }
+
+    fn len(&self, state: &State, _: &Config) -> usize { state.trash().len() }
+    const CONSTRAINTS: [Constraint; 3] = [
+        TIMESTAMP_CONSTRAINT,
+        Constraint::Fill(1),
+        Constraint::Fill(2)
+    ];
+}
+
+fn origin_to_line<'a>(item: &'a TrashItem, config: &Config) -> Line<'a> {
+    match &item.content {
+        Trashed::Project(_) => Line::from("project".italic().dark_gray()),
+        Trashed::Task {
+            project_title,
+            column,
+            ..
+        } => {
+            let column_color = config
+                .column_configs()
+                .find(|column_config| column_config.name == *column)
+                .map(|column_config| column_config.color)
+                .unwrap_or(Color::DarkGray);
+            Line::from_iter([
+                project_title.as_str().into(),
+                " / ".dark_gray(),
+                column.as_str().fg(column_color).italic()
+            ])
+        }
+    }
+}
+
+impl Keyhints for TrashView {
+    fn keyhints(&self, state: &State, config: &Config) -> impl IntoIterator<Item = (&str, &str)> {
+        const ITEM_REQUIRING_KEYHINTS: [(&str, &str); 2] =
+            [("Enter", "Restore"), ("Delete/Backspace", "Purge")];
+
+        let item_requiring_iter = self
+            .0
+            .keyhints(state, config)
+            .into_iter()
+            .chain(ITEM_REQUIRING_KEYHINTS);
+
+        (self.0.len(state, config) != 0)
+            .then_some(item_requiring_iter)
+            .into_iter()
+            .flatten()
+            .chain([("Esc", "Back to main view")])
+    }
+}
@@ -1,48 +1,37 @@
-use kraban_config::Config;
-use kraban_state::{Difficulty, Priority};
+use std::iter;
+
+use chrono::TimeDelta;
+use itertools::chain;
+use kraban_config::{Config, LevelConfig};
+use kraban_lib::{Timestamp, format_duration};
+use kraban_state::{Difficulty, Priority, State, Task};
 use ratatui::{
     layout::Constraint,
     style::{Color, Style, Stylize},
-    text::Line,
+    text::{Line, Span},
     widgets::{Block, BorderType, Borders}
 };
 use time::Date;
 
-pub const fn priority_to_color(priority: Priority) -> Color {
-    match priority {
-        Priority::Low => Color::Green,
-        Priority::Medium => Color::Yellow,
-        Priority::High => Color::Red
-    }
+pub fn priority_to_color(priority: &Priority, config: &Config) -> Color {
+    priority
+        .level(config)
+        .map_or(Color::DarkGray, |level| level.color)
 }
 
-pub fn priority_to_line<'a>(priority: Priority) -> Line<'a> {
-    let text = match priority {
-        Priority::Low => "!",
-        Priority::Medium => "!!",
-        Priority::High => "!!!"
-    };
-
-    Line::raw(text).fg(priority_to_color(priority))
+pub fn priority_to_line<'a>(priority: &Priority, config: &Config) -> Line<'a> {
+    level_to_line(priority.level(config))
 }
 
-pub fn difficulty_to_line<'a>(difficulty: Difficulty) -> Line<'a> {
-    let color = difficulty_to_color(difficulty);
-
-    let text = match difficulty {
-        Difficulty::Hard => "***",
-        Difficulty::Normal => "**",
-        Difficulty::Easy => "*"
-    };
-
-    Line::raw(text).fg(color)
+pub fn difficulty_to_line<'a>(difficulty: &Difficulty, config: &Config) -> Line<'a> {
+    level_to_line(difficulty.level(config))
 }
 
-pub fn difficulty_to_color(difficulty: Difficulty) -> Color {
-    match difficulty {
-        Difficulty::Hard => Color::Red,
-        Difficulty::Normal => Color::Yellow,
-        Difficulty::Easy => Color::Green
+/// Levels that were removed from the config are shown as a gray question mark
+fn level_to_line<'a>(level: Option<&LevelConfig>) -> Line<'a> {
+    match level {
+        Some(level) => Line::raw(level.symbol.clone()).fg(level.color),
+        None => Line::raw("?").dark_gray()
     }
 }
 
@@ -59,6 +48,60 @@ pub fn due_date_to_line(due_date: Date, now: Date) -> Line<'static> {
     due_date.to_string().fg(color).underlined().into()
 }
 
+pub fn task_number_to_line<'a>(number: u32) -> Line<'a> {
+    Line::raw(format!("#{number}")).dark_gray().right_aligned()
+}
+
+/// Appends the time tracked on the task, highlighted while its timer is running
+pub fn tracked_time_to_line<'a>(mut line: Line<'a>, task: &Task, config: &Config) -> Line<'a> {
+    let time = task.tracked_time(None);
+    let running = task.timer_started_at().is_some();
+    if running || time > TimeDelta::zero() {
+        let span = format!(" ⏱ {}", format_duration(time));
+        line.push_span(match running {
+            true => span.fg(config.app_color),
+            false => span.dark_gray()
+        });
+    }
+
+    line
+}
+
+pub fn timestamp_to_line<'a>(timestamp: Timestamp) -> Line<'a> {
+    Line::raw(timestamp.format("%Y-%m-%d").to_string()).dark_gray()
+}
+
+/// A chip colored by the tag config, tags missing from the config are gray
+pub fn tag_to_span(tag: &str, config: &Config) -> Span<'static> {
+    let color = config.tag_color(tag).unwrap_or(Color::DarkGray);
+    Span::raw(format!(" {tag} ")).bg(color).black()
+}
+
+/// The title followed by indicators of what else the task contains
+pub fn title_to_line<'a>(task: &'a Task, state: &State, config: &Config) -> Line<'a> {
+    let blocked_indicator = state.is_blocked(task).then_some(" ⊘".red());
+    let recurrence_indicator = task.recurrence.is_some().then_some(" ↻".dark_gray());
+    let notes_indicator = (!task.description.is_empty()).then_some(" ≡".dark_gray());
+    let checklist_progress = task.checklist_progress().map(|(done, total)| {
+        let span = format!(" {done}/{total}").italic();
+        match done == total {
+            true => span.green(),
+            false => span.dark_gray()
+        }
+    });
+
+    Line::from_iter(chain![
+        iter::once(task.title.as_str().into()),
+        blocked_indicator,
+        recurrence_indicator,
+        notes_indicator,
+        checklist_progress,
+        task.tags
+            .iter()
+            .flat_map(|tag| [" ".into(), tag_to_span(tag, config)])
+    ])
+}
+
 pub fn block_widget(config: &Config) -> Block<'static> {
     Block::new()
         .border_type(BorderType::Rounded)
@@ -69,3 +112,6 @@ pub fn block_widget(config: &Config) -> Block<'static> {
 pub const PRIORITY_CONSTRAINT: Constraint = Constraint::Length(3);
 pub const DIFFICULTY_CONSTRAINT: Constraint = Constraint::Length(3);
 pub const DUE_DATE_CONSTRAINT: Constraint = Constraint::Length(10);
+/// Fits up to `#99999`, numbers only grow as tasks are added, duplicated or moved to the project
+pub const TASK_NUMBER_CONSTRAINT: Constraint = Constraint::Length(6);
+pub const TIMESTAMP_CONSTRAINT: Constraint = Constraint::Length(10);
@@ -1,49 +1,68 @@
+use std::{ops::ControlFlow, time::Duration};
+
 use cli_log::debug;
 use color_eyre::Result;
-use kraban_config::Config;
-use kraban_state::State;
+use kraban_config::{Board, Config};
+use kraban_state::{State, Storage, open_storage};
 use kraban_ui::{Response, Ui};
 use ratatui::{
     DefaultTerminal,
     crossterm::event::{self, Event, KeyEvent, KeyEventKind}
 };
 
+const TIMER_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct App<'a> {
     state: State,
+    storage: Box<dyn Storage>,
     ui: Ui<'a>,
     config: &'a Config,
-    terminal: DefaultTerminal
+    terminal: &'a mut DefaultTerminal
 }
 
 impl<'a> App<'a> {
-    pub fn run(config: &'a Config) -> Result<()> {
-        let terminal = ratatui::init();
-        let state = State::new(config)?;
+    /// Returns the board to switch to when the app is left through the board switcher
+    pub fn run(config: &'a Config, terminal: &'a mut DefaultTerminal) -> Result<Option<Board>> {
+        let mut storage = open_storage(config)?;
+        let state = State::new(storage.as_mut(), config)?;
         Self {
-            ui: Ui::default(),
+            ui: Ui::new(&state, config),
             state,
+            storage,
             config,
             terminal
         }
         .main_loop()
     }
 
-    fn main_loop(mut self) -> Result<()> {
+    fn main_loop(mut self) -> Result<Option<Board>> {
+        self.draw()?;
+        match self.handle_crossterm_events()? {
+            ControlFlow::Continue(mut app) => {
+                app.state.finish_change(app.config);
+                app.state.save_if_needed(app.storage.as_mut(), app.config)?;
+                app.main_loop()
+            }
+            ControlFlow::Break(board) => Ok(board)
+        }
+    }
+
+    fn draw(&mut self) -> Result<()> {
         self.terminal.draw(|frame| {
             let area = frame.area();
             let buf = frame.buffer_mut();
             self.ui.render(area, buf, &self.state, self.config)
         })?;
 
-        if let Some(mut app) = self.handle_crossterm_events()? {
-            app.state.save_if_needed()?;
-            return app.main_loop();
-        }
-
         Ok(())
     }
 
-    fn handle_crossterm_events(mut self) -> Result<Option<Self>> {
+    fn handle_crossterm_events(mut self) -> Result<ControlFlow<Option<Board>, Self>> {
+        // The header shows the running timer, so it's redrawn while waiting for an event
+        while self.state.running_timer().is_some() && !event::poll(TIMER_REDRAW_INTERVAL)? {
+            self.draw()?;
+        }
+
         let event = event::read()?;
         debug!("{event:?}");
         match event {
@@ -52,32 +71,33 @@ impl<'a> App<'a> {
                     kind: KeyEventKind::Press,
                     ..
                 }
-            ) => return self.on_key(key),
-            Event::FocusGained => self.state = State::new(self.config)?,
+            ) => return Ok(self.on_key(key)),
+            Event::FocusGained => self.state = State::new(self.storage.as_mut(), self.config)?,
             _ => {}
         }
 
-        Ok(Some(self))
+        Ok(ControlFlow::Continue(self))
     }
 
-    fn on_key(self, key: KeyEvent) -> Result<Option<Self>> {
+    fn on_key(self, key: KeyEvent) -> ControlFlow<Option<Board>, Self> {
         let App {
             mut state,
+            storage,
             ui,
             config,
             terminal
         } = self;
 
-        let app = match ui.on_key(key, &mut state, config) {
-            Response::Quit => None,
-            Response::Update(ui) => Some(Self {
+        match ui.on_key(key, &mut state, config) {
+            Response::Quit => ControlFlow::Break(None),
+            Response::SwitchBoard(board) => ControlFlow::Break(Some(board)),
+            Response::Update(ui) => ControlFlow::Continue(Self {
                 state,
+                storage,
                 ui,
                 config,
                 terminal
             })
-        };
-
-        Ok(app)
+        }
     }
 }
@@ -1,8 +1,9 @@
+use chrono::NaiveDate;
 use clap::{
-    ArgAction, Parser,
-    builder::{Styles, styling::AnsiColor::*},
-    command
+    ArgAction, Args, Parser, Subcommand, ValueEnum,
+    builder::{Styles, styling::AnsiColor::*}
 };
+use kraban_config::StorageBackend;
 
 const STYLE: Styles = Styles::styled()
     .header(Green.on_default().bold())
@@ -16,5 +17,85 @@ pub struct Cli {
     #[arg(long, short, action = ArgAction::SetTrue, exclusive = true)]
     pub print_default_config: bool,
     #[arg(long, short, action = ArgAction::SetTrue, exclusive = true)]
-    pub write_defaul_config: bool
+    pub write_defaul_config: bool,
+    /// Board to open, each board has its own state and can have its own config in boards/<name>.toml next to kraban.toml.
+    /// Without it, the .kraban directory in the working directory or its parents is used if there is one, the default board otherwise
+    #[arg(long, short, global = true)]
+    pub board: Option<String>,
+    #[command(subcommand)]
+    pub command: Option<Command>
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Roll the state back to one of the automatic backups
+    Restore(RestoreArgs),
+    /// Copy the state from one storage backend to another
+    Convert(ConvertArgs),
+    /// List the boards, the current one is highlighted
+    Boards,
+    /// List the commits of the state made with git_history enabled
+    History(HistoryArgs),
+    /// Summarize the tasks
+    #[command(subcommand)]
+    Report(Report)
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+pub struct RestoreArgs {
+    /// List the available backups, newest first
+    #[arg(long, short, action = ArgAction::SetTrue)]
+    pub list: bool,
+    /// Name of the backup (as printed by --list) or path to a state file
+    #[arg(long, short)]
+    pub from: Option<String>
+}
+
+#[derive(Args)]
+pub struct ConvertArgs {
+    #[arg(long, short)]
+    pub from: Backend,
+    #[arg(long, short)]
+    pub to: Backend
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Json,
+    Sqlite
+}
+
+impl From<Backend> for StorageBackend {
+    fn from(backend: Backend) -> Self {
+        match backend {
+            Backend::Json => StorageBackend::Json,
+            Backend::Sqlite => StorageBackend::Sqlite
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    /// Restore the state from a commit, the current state is backed up first
+    #[arg(long, short)]
+    pub checkout: Option<String>
+}
+
+#[derive(Subcommand)]
+pub enum Report {
+    /// Time tracked with task timers per project and column
+    Time {
+        /// Only count the time tracked from the start of this day (YYYY-MM-DD)
+        #[arg(long, short)]
+        since: Option<NaiveDate>
+    },
+    /// Columns holding tasks that are missing from the config, the app asks what to do with them on startup
+    OrphanedColumns,
+    /// Lead and cycle times of the completed tasks per project and difficulty, and the weekly throughput
+    Flow {
+        /// How many weeks of throughput to show, the current one included
+        #[arg(long, short, default_value_t = 8)]
+        weeks: u32
+    }
 }
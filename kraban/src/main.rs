@@ -1,16 +1,29 @@
 mod app;
 mod cli;
 
-use std::io::stdout;
+use std::{
+    io::stdout,
+    iter::{self, chain}
+};
 
 use app::App;
-use clap::Parser;
+use chrono::{Local, NaiveTime, TimeDelta};
+use clap::{Parser, ValueEnum};
 use cli_log::init_cli_log;
-use color_eyre::{Result, eyre::Context};
-use kraban_config::Config;
-use ratatui::crossterm::{event::EnableFocusChange, execute};
+use color_eyre::{
+    Result,
+    eyre::{Context, OptionExt, bail},
+    owo_colors::OwoColorize
+};
+use kraban_config::{Board, Config};
+use kraban_lib::{format_duration, format_elapsed};
+use kraban_state::{Durations, FlowReport, FlowTimes, ProjectTime, State, open, open_storage};
+use ratatui::{
+    DefaultTerminal,
+    crossterm::{event::EnableFocusChange, execute}
+};
 
-use crate::cli::Cli;
+use crate::cli::{Cli, Command, ConvertArgs, HistoryArgs, Report, RestoreArgs};
 fn main() -> Result<()> {
     color_eyre::install()?;
     let cli = Cli::parse();
@@ -24,10 +37,217 @@ fn main() -> Result<()> {
     }
 
     init_cli_log!();
-    let config = Config::new()?;
+    let config = Config::new(Board::discover(cli.board)?)?;
+    if let Some(command) = cli.command {
+        return run_command(command, &config);
+    }
+
+    let mut terminal = ratatui::init();
     let result = execute!(stdout(), EnableFocusChange)
         .wrap_err("Failed to enable focus change")
-        .and(App::run(&config));
+        .and_then(|_| run_app(config, &mut terminal));
     ratatui::restore();
     result
 }
+
+/// Runs the app, restarting it whenever the board is switched
+fn run_app(mut config: Config, terminal: &mut DefaultTerminal) -> Result<()> {
+    while let Some(board) = App::run(&config, terminal)? {
+        config = Config::new(board)?;
+    }
+
+    Ok(())
+}
+
+fn run_command(command: Command, config: &Config) -> Result<()> {
+    match command {
+        Command::Restore(RestoreArgs { list: true, .. }) => {
+            for backup in State::backups(config)? {
+                println!("{}", backup.name);
+            }
+        }
+        Command::Restore(RestoreArgs {
+            from: Some(backup), ..
+        }) => {
+            State::restore_backup(&backup, open_storage(config)?.as_mut(), config)?;
+            println!("Restored state from {}", backup.green());
+        }
+        Command::Restore(_) => unreachable!("clap requires either --list or --from"),
+        Command::History(HistoryArgs { checkout: None }) => {
+            for commit in State::git_history(config)? {
+                let time = commit.time.format("%Y-%m-%d %H:%M");
+                println!(
+                    "{} {} {}",
+                    commit.id.yellow(),
+                    time.dimmed(),
+                    commit.message
+                );
+            }
+        }
+        Command::History(HistoryArgs {
+            checkout: Some(revision)
+        }) => {
+            State::checkout_git_revision(&revision, open_storage(config)?.as_mut(), config)?;
+            println!("Restored state from {}", revision.green());
+        }
+        Command::Report(Report::Time { since }) => {
+            let since = since
+                .map(|since| {
+                    since
+                        .and_time(NaiveTime::MIN)
+                        .and_local_timezone(Local)
+                        .earliest()
+                        .ok_or_eyre("The date doesn't exist in the local timezone")
+                })
+                .transpose()?;
+            let state = State::new(open_storage(config)?.as_mut(), config)?;
+            print_time_report(&state.time_report(since, config));
+        }
+        Command::Report(Report::OrphanedColumns) => {
+            let state = State::new(open_storage(config)?.as_mut(), config)?;
+            for orphan in state.orphaned_columns(config) {
+                println!(
+                    "{} {} {} tasks{}",
+                    orphan.project.title.bold(),
+                    orphan.column.yellow(),
+                    orphan.tasks,
+                    if orphan.hidden { " (hidden)" } else { "" }.dimmed()
+                );
+            }
+        }
+        Command::Report(Report::Flow { weeks }) => {
+            let state = State::new(open_storage(config)?.as_mut(), config)?;
+            print_flow_report(&state.flow_report(weeks, config));
+        }
+        Command::Boards => {
+            for board in Config::boards()? {
+                match board == config.board {
+                    true => println!("{}", board.green()),
+                    false => println!("{board}")
+                }
+            }
+        }
+        Command::Convert(ConvertArgs { from, to }) => {
+            if from == to {
+                bail!("Cannot convert the state to the storage it's already in")
+            }
+
+            State::convert(
+                open(from.into(), config)?.as_mut(),
+                open(to.into(), config)?.as_mut(),
+                config
+            )?;
+            println!(
+                "Converted the state, set {} in the config to use it",
+                format!(
+                    "storage = \"{}\"",
+                    to.to_possible_value()
+                        .expect("No values are skipped")
+                        .get_name()
+                )
+                .green()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn print_time_report(report: &[ProjectTime]) {
+    let width = report
+        .iter()
+        .flat_map(|project| {
+            let columns = project.columns.iter().map(|(column, _)| column.len() + 2);
+            iter::once(project.project.title.len()).chain(columns)
+        })
+        .max()
+        .unwrap_or_default();
+
+    let mut total = TimeDelta::zero();
+    for ProjectTime { project, columns } in report {
+        let project_time: TimeDelta = columns.iter().map(|(_, time)| *time).sum();
+        total += project_time;
+        println!(
+            "{:width$} {}",
+            project.title.bold(),
+            format_duration(project_time).green()
+        );
+        for (column, time) in columns {
+            println!(
+                "  {column:width$} {}",
+                format_duration(*time),
+                width = width - 2
+            );
+        }
+    }
+
+    println!(
+        "{:width$} {}",
+        "Total".bold(),
+        format_duration(total).green()
+    );
+}
+
+fn print_flow_report(report: &FlowReport) {
+    let names = chain(
+        report
+            .projects
+            .iter()
+            .map(|(project, _)| project.title.as_str()),
+        report
+            .difficulties
+            .iter()
+            .map(|(difficulty, _)| difficulty.unwrap_or("None"))
+    );
+    let width = names.map(str::len).max().unwrap_or_default().max(12);
+
+    let header = |title: &str| {
+        println!(
+            "{:width$} {:>5}  {:^35}  {:^35}",
+            title.bold(),
+            "done",
+            "lead time (avg/p50/p85/p95)",
+            "cycle time (avg/p50/p85/p95)"
+        )
+    };
+    let row = |name: &str, times: &FlowTimes| {
+        let count = times.lead.as_ref().map_or(0, |lead| lead.count);
+        println!(
+            "{name:width$} {count:>5}  {}  {}",
+            flow_durations(times.lead.as_ref()),
+            flow_durations(times.cycle.as_ref())
+        )
+    };
+
+    header("Project");
+    for (project, times) in &report.projects {
+        row(&project.title, times);
+    }
+
+    println!();
+    header("Difficulty");
+    for (difficulty, times) in &report.difficulties {
+        row(difficulty.unwrap_or("None"), times);
+    }
+
+    println!();
+    println!("{}", "Weekly throughput".bold());
+    for (week, done) in &report.throughput {
+        println!("{week} {} {done}", "█".repeat(*done).green());
+    }
+}
+
+fn flow_durations(durations: Option<&Durations>) -> String {
+    let Some(durations) = durations else {
+        return format!("{:^35}", "-");
+    };
+
+    [
+        durations.average,
+        durations.p50,
+        durations.p85,
+        durations.p95
+    ]
+    .map(|duration| format!("{:>8}", format_elapsed(duration)))
+    .join(" ")
+}